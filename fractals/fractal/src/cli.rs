@@ -0,0 +1,774 @@
+//! Command line of the `fractal` binary, kept apart from nannou so it can be tested
+//! without opening a window.
+
+use std::ffi::OsString;
+use std::path::PathBuf;
+
+use clap::builder::PossibleValue;
+use clap::error::ErrorKind;
+use clap::{Arg, ArgAction, ArgMatches, Command, ValueEnum};
+use fractal_export::{parse_size, Export};
+use fractal_geometry::cantor::Mask;
+use fractal_geometry::circles::{BranchPreset, BranchShape, Branching};
+use fractal_geometry::drawing::Rgb;
+use fractal_geometry::koch::{KochParams, KochType};
+use fractal_geometry::lsystem::Preset;
+use fractal_geometry::sierpinski::SierpinskiShape;
+use fractal_scene::{
+    Apollonian, Cantor, Circles, Coloring, Fractal, Koch, LiveScene, Render, Scene, Sierpinski,
+};
+
+const EXAMPLES: &str = "\
+Examples:
+  fractal koch --sides 6 --depth 4
+  fractal koch --type linear --variant cesaro --depth 5 --png cesaro.png
+  fractal koch --type linear --depth 6 --dimension
+  fractal sierpinski --depth 6 --color '#1e90ff' --svg sierpinski.svg
+  fractal sierpinski --shape flake --sides 5 --equilateral --depth 4
+  fractal sierpinski --equilateral --chaos-game 200000 --png chaos.png
+  fractal sierpinski --depth 5 --render inverted --coloring gradient --color '#1e90ff,#ff8000'
+  fractal cantor --stroke 6 --size 1600x400 --png cantor.png --window
+  fractal cantor --mask 10101 --widths 1,2,1,2,1 --depth 4
+  fractal cantor --dust --depth 4
+  fractal cantor --fit --depth 5 --size 800x300
+  fractal circles --preset vicsek --radius 300 --depth 4
+  fractal circles --children 6 --ratio 0.33 --rotation 15 --shape polygon --sides 3
+  fractal apollonian --curvatures -1,2,2 --filled --coloring gradient --color '#1e90ff,#ff8000'
+  fractal apollonian --curvatures -6,11,14 --min-radius 2 --svg gasket.svg
+  fractal lsystem --preset dragon --depth 12 --frames frames/
+  fractal koch --sides 5 --star 2 --save-scene star.toml
+  fractal --scene star.toml";
+
+// Window size for `--scene`, which has no subcommand to take `--size` from
+const SCENE_SIZE: (u32, u32) = (800, 600);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KochKind {
+    Linear,
+    Radial,
+}
+
+impl ValueEnum for KochKind {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[KochKind::Linear, KochKind::Radial]
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        Some(match self {
+            KochKind::Linear => PossibleValue::new("linear").help("One curve across the window"),
+            KochKind::Radial => PossibleValue::new("radial").help("Curves around a polygon"),
+        })
+    }
+}
+
+/// Where the sketch goes.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Output {
+    pub export: Export,
+    pub frames: Option<PathBuf>, // Directory for one PNG per depth, from 0 up to the scene's
+    pub save_scene: Option<PathBuf>,
+    pub window: bool,    // Open the window after writing any files
+    pub dimension: bool, // Print a box-counting estimate of the dimension
+}
+
+/// A validated command line.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Options {
+    pub scene: LiveScene, // Watched for changes when it came from `--scene`
+    pub size: (u32, u32), // Window size, and the size of every file written
+    pub output: Output,
+}
+
+pub fn command() -> Command {
+    Command::new("fractal")
+        .version("1.0")
+        .about("Draws fractals in a window or into SVG and PNG files")
+        .after_help(EXAMPLES)
+        .arg_required_else_help(true)
+        .arg(
+            Arg::new("scene")
+                .long("scene")
+                .value_name("FILE")
+                .value_parser(clap::value_parser!(PathBuf))
+                .help("Draw a TOML or JSON scene file instead of a subcommand, redrawn when it changes"),
+        )
+        .arg(
+            Arg::new("save-scene")
+                .long("save-scene")
+                .global(true)
+                .value_name("FILE")
+                .value_parser(clap::value_parser!(PathBuf))
+                .help("Write the settings to a scene file, TOML unless it ends in .json"),
+        )
+        .arg(
+            Arg::new("window")
+                .long("window")
+                .global(true)
+                .action(ArgAction::SetTrue)
+                .help("Open the window even when writing files"),
+        )
+        .arg(
+            Arg::new("dimension")
+                .long("dimension")
+                .global(true)
+                .action(ArgAction::SetTrue)
+                .help("Print a box-counting estimate of the dimension next to the theory"),
+        )
+        .arg(
+            Arg::new("svg")
+                .long("svg")
+                .global(true)
+                .value_name("FILE")
+                .value_parser(clap::value_parser!(PathBuf))
+                .help("Write the fractal to an SVG file instead of opening a window"),
+        )
+        .arg(
+            Arg::new("png")
+                .long("png")
+                .global(true)
+                .value_name("FILE")
+                .value_parser(clap::value_parser!(PathBuf))
+                .help("Write the fractal to a PNG file instead of opening a window"),
+        )
+        .arg(
+            Arg::new("frames")
+                .long("frames")
+                .global(true)
+                .value_name("DIR")
+                .value_parser(clap::value_parser!(PathBuf))
+                .help("Write one PNG per depth, from 0 up to --depth, into a directory"),
+        )
+        .subcommand(
+            Command::new("koch")
+                .about("Koch curves, straight or around a polygon")
+                .arg(
+                    Arg::new("type")
+                        .short('t')
+                        .long("type")
+                        .value_parser(clap::value_parser!(KochKind))
+                        .default_value("radial")
+                        .help("Type of Koch curve"),
+                )
+                .arg(
+                    Arg::new("sides")
+                        .short('s')
+                        .long("sides")
+                        .value_parser(clap::value_parser!(u32).range(3..))
+                        .default_value("12")
+                        .help("Number of sides for radial"),
+                )
+                .arg(
+                    Arg::new("star")
+                        .long("star")
+                        .value_name("K")
+                        .value_parser(clap::value_parser!(u32).range(1..))
+                        .default_value("1")
+                        .help("Connect every K-th vertex, e.g. `--sides 5 --star 2`"),
+                )
+                .arg(
+                    Arg::new("variant")
+                        .long("variant")
+                        .value_parser(["koch", "cesaro", "anti-snowflake"])
+                        .default_value("koch")
+                        .help("Shape of the bumps"),
+                )
+                .arg(depth("4", 32))
+                .args(look("4", "800x600")),
+        )
+        .subcommand(
+            Command::new("sierpinski")
+                .about("Sierpinski triangle, carpet and n-flakes")
+                .arg(
+                    Arg::new("shape")
+                        .long("shape")
+                        .value_parser(["triangle", "carpet", "flake"])
+                        .default_value("triangle")
+                        .help("Member of the Sierpinski family"),
+                )
+                .arg(
+                    Arg::new("sides")
+                        .short('s')
+                        .long("sides")
+                        .value_parser(clap::value_parser!(u32).range(3..))
+                        .default_value("5")
+                        .help("Number of sides for flake, 5 for the pentaflake"),
+                )
+                .arg(
+                    Arg::new("equilateral")
+                        .long("equilateral")
+                        .action(ArgAction::SetTrue)
+                        .help("Keep the shape's proportions instead of stretching it over the window"),
+                )
+                .arg(
+                    Arg::new("render")
+                        .long("render")
+                        .value_parser(["filled", "outline", "inverted"])
+                        .default_value("filled")
+                        .help("Fill the polygons, outline every level or fill the holes"),
+                )
+                .arg(
+                    Arg::new("coloring")
+                        .long("coloring")
+                        .value_parser(["shapes", "levels", "gradient"])
+                        .default_value("shapes")
+                        .help("Give each polygon the next --color, one per level, or a gradient over the levels"),
+                )
+                .arg(
+                    Arg::new("chaos-game")
+                        .long("chaos-game")
+                        .value_name("POINTS")
+                        .value_parser(
+                            clap::value_parser!(u32).range(1..=i64::from(Sierpinski::MAX_POINTS)),
+                        )
+                        .help("Draw this many points with the chaos game instead of polygons"),
+                )
+                .arg(depth("3", 16))
+                .args(look("0", "1024x768")),
+        )
+        .subcommand(
+            Command::new("cantor")
+                .about("Cantor sets, one row of bars per split, or Cantor dust")
+                .arg(
+                    Arg::new("mask")
+                        .long("mask")
+                        .value_parser(|mask: &str| mask.parse::<Mask>())
+                        .default_value("101")
+                        .help("Parts to keep (1) and remove (0) at every split, e.g. 10101 or 1001"),
+                )
+                .arg(
+                    Arg::new("widths")
+                        .long("widths")
+                        .value_delimiter(',')
+                        .value_parser(clap::value_parser!(f32))
+                        .help("Relative widths of the mask's parts, e.g. 1,2,1, equal if left out"),
+                )
+                .arg(
+                    Arg::new("fat")
+                        .long("fat")
+                        .action(ArgAction::SetTrue)
+                        .help("The Smith-Volterra-Cantor set, which keeps half its length"),
+                )
+                .arg(
+                    Arg::new("fit")
+                        .long("fit")
+                        .action(ArgAction::SetTrue)
+                        .help("Spread the rows over the window's height instead of 30px apart"),
+                )
+                .arg(
+                    Arg::new("dust")
+                        .long("dust")
+                        .action(ArgAction::SetTrue)
+                        .help("Draw the 2D Cantor dust instead of rows of bars"),
+                )
+                .arg(depth("7", 32))
+                .args(look("10", "1024x768")),
+        )
+        .subcommand(
+            Command::new("circles")
+                .about("Recursive circles, squares or polygons, each with smaller copies around it")
+                .arg(
+                    Arg::new("radius")
+                        .long("radius")
+                        .value_parser(clap::value_parser!(f32))
+                        .default_value("500")
+                        .help("Radius of the largest shape"),
+                )
+                .arg(
+                    Arg::new("preset")
+                        .short('p')
+                        .long("preset")
+                        .value_name("NAME")
+                        .value_parser(|name: &str| name.parse::<BranchPreset>())
+                        .default_value("four")
+                        .help("four, two, apollonian or vicsek, the flags below override it"),
+                )
+                .arg(
+                    Arg::new("children")
+                        .long("children")
+                        .value_parser(clap::value_parser!(u32).range(1..=64))
+                        .conflicts_with("angles")
+                        .help("Children spread evenly around every shape"),
+                )
+                .arg(
+                    Arg::new("angles")
+                        .long("angles")
+                        .value_delimiter(',')
+                        .allow_negative_numbers(true)
+                        .value_parser(clap::value_parser!(f32))
+                        .help("Direction of every child in degrees, e.g. 0,120,240"),
+                )
+                .arg(
+                    Arg::new("center")
+                        .long("center")
+                        .action(ArgAction::SetTrue)
+                        .help("Add a child in the middle of every shape"),
+                )
+                .arg(
+                    Arg::new("distance")
+                        .long("distance")
+                        .value_parser(clap::value_parser!(f32))
+                        .help("From a shape's center to its children's, in radii of the shape"),
+                )
+                .arg(
+                    Arg::new("ratio")
+                        .long("ratio")
+                        .value_parser(clap::value_parser!(f32))
+                        .help("Size of a child relative to its parent"),
+                )
+                .arg(
+                    Arg::new("rotation")
+                        .long("rotation")
+                        .allow_negative_numbers(true)
+                        .value_parser(clap::value_parser!(f32))
+                        .help("Degrees every level turns further than the one above"),
+                )
+                .arg(
+                    Arg::new("shape")
+                        .long("shape")
+                        .value_parser(["circle", "square", "polygon"])
+                        .help("What every copy is drawn as"),
+                )
+                .arg(
+                    Arg::new("sides")
+                        .short('s')
+                        .long("sides")
+                        .value_parser(clap::value_parser!(u32).range(3..))
+                        .default_value("6")
+                        .help("Number of sides for polygon"),
+                )
+                .arg(
+                    Arg::new("min-radius")
+                        .long("min-radius")
+                        .value_parser(clap::value_parser!(f32))
+                        .help("Shapes this small or smaller have no children"),
+                )
+                .arg(depth("6", 32))
+                .args(look("2", "1024x768")),
+        )
+        .subcommand(
+            Command::new("apollonian")
+                .about("Apollonian gasket, circles filling the gaps between touching circles")
+                .arg(
+                    Arg::new("curvatures")
+                        .long("curvatures")
+                        .value_name("K1,K2,K3")
+                        .value_delimiter(',')
+                        .allow_hyphen_values(true)
+                        .value_parser(clap::value_parser!(f64))
+                        .default_value("-1,2,2")
+                        .help("Curvatures of three touching circles to start from, negative for one around the others"),
+                )
+                .arg(
+                    Arg::new("min-radius")
+                        .long("min-radius")
+                        .value_parser(clap::value_parser!(f32))
+                        .default_value("1")
+                        .help("Leave out circles smaller than this, in pixels"),
+                )
+                .arg(
+                    Arg::new("filled")
+                        .long("filled")
+                        .action(ArgAction::SetTrue)
+                        .help("Fill the circles instead of outlining them"),
+                )
+                .arg(
+                    Arg::new("coloring")
+                        .long("coloring")
+                        .value_parser(["shapes", "levels", "gradient"])
+                        .default_value("shapes")
+                        .help("Give each circle the next --color, one per generation, or a gradient over the curvatures"),
+                )
+                .arg(depth("8", 32))
+                .args(look("1", "800x800")),
+        )
+        .subcommand(
+            Command::new("lsystem")
+                .about("L-system presets drawn with a turtle")
+                .arg(
+                    Arg::new("preset")
+                        .short('p')
+                        .long("preset")
+                        .value_name("NAME")
+                        .value_parser(|name: &str| name.parse::<Preset>())
+                        .default_value("koch")
+                        .help(
+                            "koch, snowflake, quadratic-koch, dragon, hilbert, gosper, plant, \
+                             bush, coastline or context-plant",
+                        ),
+                )
+                .arg(
+                    Arg::new("seed")
+                        .long("seed")
+                        .value_parser(clap::value_parser!(u64))
+                        .default_value("0")
+                        .help("Seed for presets with random rules"),
+                )
+                .arg(depth("4", 32))
+                .args(look("2", "800x600")),
+        )
+}
+
+// `--coloring`, for the fractals that have it
+fn coloring(args: &ArgMatches) -> Coloring {
+    match args.get_one::<String>("coloring").unwrap().as_str() {
+        "levels" => Coloring::Levels,
+        "gradient" => Coloring::Gradient,
+        _ => Coloring::Shapes,
+    }
+}
+
+// `--depth` with a default and an upper bound for one subcommand
+fn depth(default: &'static str, max: u32) -> Arg {
+    Arg::new("depth")
+        .short('d')
+        .long("depth")
+        .value_parser(clap::value_parser!(u32).range(..=i64::from(max)))
+        .default_value(default)
+        .help("Depth of recursion")
+}
+
+// Colors, stroke and size, with the defaults of the sketch the subcommand replaces
+fn look(stroke: &'static str, size: &'static str) -> [Arg; 4] {
+    [
+        Arg::new("color")
+            .long("color")
+            .action(ArgAction::Append)
+            .value_delimiter(',')
+            .value_parser(|color: &str| color.parse::<Rgb>())
+            .default_value("black")
+            .help("Color of the fractal: black, white or #rrggbb, repeat for a palette"),
+        Arg::new("background")
+            .long("background")
+            .value_parser(|color: &str| color.parse::<Rgb>())
+            .default_value("white")
+            .help("Background color: black, white or #rrggbb"),
+        Arg::new("stroke")
+            .long("stroke")
+            .value_parser(clap::value_parser!(f32))
+            .default_value(stroke)
+            .help("Line width"),
+        Arg::new("size")
+            .long("size")
+            .value_name("WxH")
+            .value_parser(parse_size)
+            .default_value(size)
+            .help("Size of the window and of every file written"),
+    ]
+}
+
+/// Parses and validates a command line, program name first.
+pub fn parse_from<I, T>(args: I) -> Result<Options, clap::Error>
+where
+    I: IntoIterator<Item = T>,
+    T: Into<OsString> + Clone,
+{
+    let mut command = command();
+    let matches = command.try_get_matches_from_mut(args)?;
+    options(&matches).map_err(|message| command.error(ErrorKind::ValueValidation, message))
+}
+
+// Everything clap cannot check on its own
+fn options(matches: &ArgMatches) -> Result<Options, String> {
+    let (scene, size) = match (matches.get_one::<PathBuf>("scene"), matches.subcommand()) {
+        (Some(path), None) => {
+            let scene = LiveScene::watch(path).map_err(|err| err.to_string())?;
+            (scene, SCENE_SIZE)
+        }
+        (None, Some((name, args))) => {
+            let size = *args.get_one::<(u32, u32)>("size").unwrap();
+            (LiveScene::new(subcommand_scene(name, args)?), size)
+        }
+        (Some(_), Some((name, _))) => {
+            return Err(format!(
+                "--scene replaces the subcommand, leave out either or `{name}`"
+            ))
+        }
+        (None, None) => return Err("expected a subcommand or --scene".to_string()),
+    };
+
+    Ok(Options {
+        scene,
+        size,
+        output: Output {
+            export: Export {
+                svg: matches.get_one::<PathBuf>("svg").cloned(),
+                png: matches.get_one::<PathBuf>("png").cloned(),
+                size: Some(size),
+            },
+            frames: matches.get_one::<PathBuf>("frames").cloned(),
+            save_scene: matches.get_one::<PathBuf>("save-scene").cloned(),
+            window: matches.get_flag("window"),
+            dimension: matches.get_flag("dimension"),
+        },
+    })
+}
+
+// The scene a subcommand and its arguments describe
+fn subcommand_scene(name: &str, args: &ArgMatches) -> Result<Scene, String> {
+    // Every argument with a default is always present
+    let value = |id: &str| *args.get_one::<u32>(id).unwrap();
+
+    let fractal = match name {
+        "koch" => {
+            let koch_type = match args.get_one::<KochKind>("type").unwrap() {
+                KochKind::Linear => KochType::Linear,
+                KochKind::Radial => KochType::Radial(value("sides")),
+            };
+            let generator = match args.get_one::<String>("variant").unwrap().as_str() {
+                "cesaro" => KochParams::cesaro(85.0),
+                "anti-snowflake" => KochParams::anti_snowflake(),
+                _ => KochParams::default(),
+            };
+            // Linear curves have no polygon, whatever `--sides` and `--star` say
+            if matches!(koch_type, KochType::Radial(sides) if sides <= 2 * value("star")) {
+                return Err(format!(
+                    "a star polygon needs more than twice as many sides as skipped vertices, \
+                     got --sides {} --star {}",
+                    value("sides"),
+                    value("star")
+                ));
+            }
+            Fractal::Koch(Koch::new(koch_type, value("star"), generator))
+        }
+        "sierpinski" => {
+            let shape = match args.get_one::<String>("shape").unwrap().as_str() {
+                "carpet" => SierpinskiShape::Carpet,
+                "flake" => SierpinskiShape::Flake(value("sides")),
+                _ => SierpinskiShape::Triangle,
+            };
+            let render = match args.get_one::<String>("render").unwrap().as_str() {
+                "outline" => Render::Outline,
+                "inverted" => Render::Inverted,
+                _ => Render::Filled,
+            };
+            Fractal::Sierpinski(Sierpinski {
+                equilateral: args.get_flag("equilateral"),
+                render,
+                coloring: coloring(args),
+                points: args.get_one("chaos-game").copied(),
+                ..Sierpinski::new(shape)
+            })
+        }
+        "cantor" => Fractal::Cantor(Cantor {
+            mask: args.get_one::<Mask>("mask").unwrap().clone(),
+            widths: args
+                .get_many("widths")
+                .map_or(Vec::new(), |widths| widths.copied().collect()),
+            fat: args.get_flag("fat"),
+            dust: args.get_flag("dust"),
+            fit: args.get_flag("fit"),
+        }),
+        "circles" => {
+            let mut branching = args.get_one::<BranchPreset>("preset").unwrap().branching();
+            if let Some(&children) = args.get_one("children") {
+                branching.angles = Branching::evenly(children);
+            }
+            if let Some(angles) = args.get_many("angles") {
+                branching.angles = angles.copied().collect();
+            }
+            branching.center |= args.get_flag("center");
+            for (id, setting) in [
+                ("distance", &mut branching.distance),
+                ("ratio", &mut branching.ratio),
+                ("rotation", &mut branching.rotation),
+                ("min-radius", &mut branching.min_radius),
+            ] {
+                if let Some(&given) = args.get_one(id) {
+                    *setting = given;
+                }
+            }
+            branching.shape = match args.get_one::<String>("shape").map(String::as_str) {
+                Some("circle") => BranchShape::Circle,
+                Some("square") => BranchShape::Square,
+                Some("polygon") => BranchShape::Polygon(value("sides")),
+                _ => branching.shape,
+            };
+            Fractal::Circles(Circles::new(*args.get_one("radius").unwrap(), branching))
+        }
+        "apollonian" => {
+            let curvatures: Vec<f64> = args.get_many("curvatures").unwrap().copied().collect();
+            let curvatures = <[f64; 3]>::try_from(curvatures).map_err(|curvatures| {
+                format!(
+                    "--curvatures takes three curvatures, got {}",
+                    curvatures.len()
+                )
+            })?;
+            Fractal::Apollonian(Apollonian {
+                curvatures,
+                min_radius: *args.get_one("min-radius").unwrap(),
+                filled: args.get_flag("filled"),
+                coloring: coloring(args),
+            })
+        }
+        "lsystem" => Fractal::Lsystem {
+            preset: *args.get_one("preset").unwrap(),
+            seed: *args.get_one("seed").unwrap(),
+        },
+        _ => unreachable!("clap only accepts the subcommands above"),
+    };
+
+    let scene = Scene {
+        depth: value("depth"),
+        stroke: *args.get_one("stroke").unwrap(),
+        background: *args.get_one("background").unwrap(),
+        palette: args.get_many("color").unwrap().copied().collect(),
+        fractal,
+        ..Scene::default()
+    };
+    scene.validate()?;
+    Ok(scene)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fractal_geometry::drawing::{BLACK, WHITE};
+
+    fn parse(args: &str) -> Result<Options, clap::Error> {
+        parse_from(std::iter::once("fractal").chain(args.split_whitespace()))
+    }
+
+    #[test]
+    fn every_subcommand_has_its_own_defaults() {
+        let koch = parse("koch").unwrap();
+        assert_eq!(koch.scene.scene().depth, 4);
+        assert_eq!(koch.scene.scene().stroke, 4.0);
+        assert_eq!(koch.size, (800, 600));
+
+        let sierpinski = parse("sierpinski").unwrap();
+        assert_eq!(
+            sierpinski.scene.scene().fractal,
+            Fractal::Sierpinski(Sierpinski::default())
+        );
+        assert_eq!(sierpinski.scene.scene().depth, 3);
+        assert_eq!(sierpinski.size, (1024, 768));
+
+        for name in ["cantor", "circles", "apollonian", "lsystem"] {
+            let options = parse(name).unwrap();
+            assert_eq!(options.scene.scene().palette, [BLACK]);
+            assert_eq!(options.scene.scene().background, WHITE);
+        }
+    }
+
+    #[test]
+    fn sierpinski_flags_pick_the_shape() {
+        let options = parse(
+            "sierpinski --shape flake --sides 6 --equilateral --render outline --chaos-game 500",
+        );
+        let fractal = options.unwrap().scene.scene().fractal.clone();
+        let Fractal::Sierpinski(sierpinski) = fractal else {
+            panic!("expected a Sierpinski fractal, got {fractal:?}");
+        };
+        assert_eq!(sierpinski.shape(), SierpinskiShape::Flake(6));
+        assert!(sierpinski.equilateral);
+        assert_eq!(sierpinski.render, Render::Outline);
+        assert_eq!(sierpinski.points, Some(500));
+
+        let options = parse("sierpinski --coloring levels --color #ff0000,black --color white");
+        let scene = options.unwrap().scene.scene().clone();
+        assert_eq!(scene.palette, [Rgb::new(255, 0, 0), BLACK, WHITE]);
+    }
+
+    #[test]
+    fn circle_flags_override_the_preset() {
+        let options = parse("circles --preset vicsek --rotation -15 --shape polygon --sides 7");
+        let fractal = options.unwrap().scene.scene().fractal.clone();
+        let Fractal::Circles(circles) = fractal else {
+            panic!("expected circles, got {fractal:?}");
+        };
+        let branching = circles.branching();
+        assert_eq!(branching.angles, [0.0, 90.0, 180.0, 270.0]);
+        assert!(branching.center);
+        assert_eq!(branching.rotation, -15.0);
+        assert_eq!(branching.shape, BranchShape::Polygon(7));
+
+        let options = parse("circles --children 3 --ratio 0.4").unwrap();
+        let Fractal::Circles(circles) = &options.scene.scene().fractal else {
+            panic!("expected circles");
+        };
+        assert_eq!(circles.angles, [0.0, 120.0, 240.0]);
+        assert_eq!(circles.preset(), None);
+        assert!(parse("circles --ratio 1.5").is_err());
+        assert!(parse("circles --children 3 --angles 0,90").is_err());
+    }
+
+    #[test]
+    fn apollonian_flags_pick_the_starting_circles() {
+        let options = parse("apollonian --curvatures -6,11,14 --filled --coloring levels");
+        let fractal = options.unwrap().scene.scene().fractal.clone();
+        let Fractal::Apollonian(gasket) = fractal else {
+            panic!("expected an Apollonian gasket, got {fractal:?}");
+        };
+        assert_eq!(gasket.curvatures, [-6.0, 11.0, 14.0]);
+        assert!(gasket.filled);
+        assert_eq!(gasket.coloring, Coloring::Levels);
+
+        assert!(parse("apollonian --curvatures -1,2").is_err());
+        assert!(parse("apollonian --curvatures 1,1,10").is_err());
+    }
+
+    #[test]
+    fn output_flags_are_shared_by_every_subcommand() {
+        let options = parse("cantor --png cantor.png --window --size 400x300").unwrap();
+        assert_eq!(options.output.export.png, Some(PathBuf::from("cantor.png")));
+        assert_eq!(options.output.export.size, Some((400, 300)));
+        assert!(options.output.window);
+        assert!(!options.output.dimension);
+
+        let options =
+            parse("--frames out lsystem --preset dragon --color #ff8000 --dimension").unwrap();
+        assert_eq!(options.output.frames, Some(PathBuf::from("out")));
+        assert!(options.output.dimension);
+        assert_eq!(options.scene.scene().palette, [Rgb::new(255, 128, 0)]);
+    }
+
+    #[test]
+    fn bad_arguments_are_errors() {
+        let error = |args: &str| parse(args).unwrap_err().kind();
+        assert_eq!(
+            error(""),
+            ErrorKind::DisplayHelpOnMissingArgumentOrSubcommand
+        );
+        assert_eq!(error("spiral"), ErrorKind::InvalidSubcommand);
+        assert_eq!(error("koch --sides 2"), ErrorKind::ValueValidation);
+        assert_eq!(error("koch --sides 6 --star 3"), ErrorKind::ValueValidation);
+        assert!(parse("koch --type linear --sides 4 --star 3").is_ok());
+        assert_eq!(error("sierpinski --depth 17"), ErrorKind::ValueValidation);
+        assert_eq!(
+            error("sierpinski --shape carpet --depth 9"),
+            ErrorKind::ValueValidation
+        );
+        assert_eq!(
+            error("sierpinski --chaos-game 0"),
+            ErrorKind::ValueValidation
+        );
+        assert_eq!(error("cantor --color teal"), ErrorKind::ValueValidation);
+        assert_eq!(error("cantor --mask 12"), ErrorKind::ValueValidation);
+        assert_eq!(error("cantor --widths 1,2"), ErrorKind::ValueValidation);
+        assert_eq!(error("koch --depth 15"), ErrorKind::ValueValidation);
+        assert_eq!(error("circles --star 2"), ErrorKind::UnknownArgument);
+        assert_eq!(error("--window"), ErrorKind::ValueValidation);
+        assert_eq!(error("--scene missing.toml"), ErrorKind::ValueValidation);
+    }
+
+    #[test]
+    fn scene_files_replace_the_subcommand() {
+        let path = std::env::temp_dir().join(format!("fractal_cli_{}.toml", std::process::id()));
+        let saved = parse("sierpinski --depth 5 --save-scene ignored.toml").unwrap();
+        assert_eq!(saved.output.save_scene, Some(PathBuf::from("ignored.toml")));
+        saved.scene.scene().save(&path).unwrap();
+
+        let args = format!("--scene {} --png out.png", path.display());
+        let options = parse(&args).unwrap();
+        assert_eq!(options.scene.scene(), saved.scene.scene());
+        assert_eq!(options.scene.path(), Some(path.as_path()));
+        assert_eq!(options.size, SCENE_SIZE);
+        assert_eq!(
+            parse(&format!("--scene {} koch", path.display()))
+                .unwrap_err()
+                .kind(),
+            ErrorKind::ValueValidation
+        );
+        std::fs::remove_file(path).unwrap();
+    }
+}
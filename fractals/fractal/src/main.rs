@@ -0,0 +1,79 @@
+mod cli;
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use fractal_export::png;
+use fractal_nannou::SketchConfig;
+use fractal_scene::Scene;
+
+fn main() {
+    // Bad arguments print a message and exit with a non-zero code
+    let options = cli::parse_from(std::env::args_os()).unwrap_or_else(|err| err.exit());
+    let output = &options.output;
+
+    let config = SketchConfig::new(options.size, options.scene).export(output.export.clone());
+    let scene = config.params.scene();
+    let written = config
+        .export_with(|width, height| scene.drawing(width, height))
+        .and_then(|exported| match &output.frames {
+            Some(dir) => write_frames(scene, options.size, dir).map(|()| true),
+            None => Ok(exported),
+        })
+        .and_then(|written| match &output.save_scene {
+            Some(path) => scene.save(path).map(|()| true),
+            None => Ok(written),
+        })
+        .map(|written| {
+            if output.dimension {
+                print_dimension(scene, options.size);
+            }
+            written || output.dimension
+        });
+    match written {
+        Ok(true) if !output.window => return,
+        Ok(_) => {}
+        Err(err) => {
+            eprintln!("error: failed to export: {err}");
+            std::process::exit(1);
+        }
+    }
+
+    fractal_nannou::open_scene(config);
+}
+
+// One PNG per depth, so the fractal can be watched growing
+fn write_frames(scene: &Scene, (width, height): (u32, u32), dir: &Path) -> io::Result<()> {
+    fs::create_dir_all(dir)?;
+    for depth in 0..=scene.depth {
+        let drawing = scene.at_depth(depth).drawing(width as f32, height as f32);
+        png::save(
+            &drawing,
+            width,
+            height,
+            dir.join(format!("frame_{depth:03}.png")),
+        )?;
+    }
+    Ok(())
+}
+
+// The counted boxes and fitted line, then how far they land from the formula
+fn print_dimension(scene: &Scene, (width, height): (u32, u32)) {
+    let Some(estimate) = scene.box_counting(width as f32, height as f32) else {
+        println!(
+            "{} has too little detail to count boxes over, try a greater depth",
+            scene.fractal
+        );
+        return;
+    };
+    println!("{estimate}");
+    println!("estimate: {:.4}", estimate.dimension());
+    match scene.theory() {
+        Some(theory) => {
+            let off = (estimate.dimension() / theory.value() - 1.0) * 100.0;
+            println!("theory: {theory}, {off:+.1}% away");
+        }
+        None => println!("theory: no formula for {}", scene.fractal),
+    }
+}
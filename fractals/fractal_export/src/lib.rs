@@ -0,0 +1,81 @@
+//! Headless output for the fractal sketches. Nothing in here needs a window or a GPU.
+
+use std::io;
+use std::path::PathBuf;
+
+use fractal_geometry::drawing::Drawing;
+
+pub mod png;
+pub mod svg;
+
+/// Files a sketch was asked to write instead of opening its window.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Export {
+    pub svg: Option<PathBuf>,
+    pub png: Option<PathBuf>,
+    pub size: Option<(u32, u32)>,
+}
+
+impl Export {
+    // Looks for `--svg <path>`, `--png <path>` and `--size <w>x<h>` among the process
+    // arguments, for sketches that do not parse any other arguments
+    pub fn from_args() -> Result<Self, String> {
+        Export::parse_from(std::env::args())
+    }
+
+    /// Same as `from_args`, over any arguments. Other arguments are left alone, but a
+    /// flag without a value after it is an error.
+    pub fn parse_from(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let args: Vec<String> = args.into_iter().collect();
+        let value = |flag: &str, expected: &str| -> Result<Option<String>, String> {
+            let Some(position) = args.iter().position(|arg| arg == flag) else {
+                return Ok(None);
+            };
+            match args.get(position + 1) {
+                Some(value) if !value.starts_with("--") => Ok(Some(value.clone())),
+                _ => Err(format!("{flag} needs {expected} after it")),
+            }
+        };
+
+        Ok(Export {
+            svg: value("--svg", "a path")?.map(PathBuf::from),
+            png: value("--png", "a path")?.map(PathBuf::from),
+            size: value("--size", "a size like 2048x2048")?
+                .map(|size| parse_size(&size))
+                .transpose()?,
+        })
+    }
+
+    pub fn is_requested(&self) -> bool {
+        self.svg.is_some() || self.png.is_some()
+    }
+
+    // Output size, falling back to the size of the sketch's window
+    pub fn size_or(&self, window: (u32, u32)) -> (u32, u32) {
+        self.size.unwrap_or(window)
+    }
+
+    pub fn save(&self, drawing: &Drawing) -> io::Result<()> {
+        if let Some(path) = &self.svg {
+            svg::save(drawing, path)?;
+        }
+        if let Some(path) = &self.png {
+            let size = (drawing.width.round() as u32, drawing.height.round() as u32);
+            let (width, height) = self.size.unwrap_or(size);
+            png::save(drawing, width, height, path)?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses an image size written as `<width>x<height>`, e.g. `2048x2048`.
+pub fn parse_size(size: &str) -> Result<(u32, u32), String> {
+    let invalid = || format!("invalid size `{size}`, expected <width>x<height> like 2048x2048");
+    let (width, height) = size.split_once('x').ok_or_else(invalid)?;
+    let width: u32 = width.trim().parse().map_err(|_| invalid())?;
+    let height: u32 = height.trim().parse().map_err(|_| invalid())?;
+    if width == 0 || height == 0 {
+        return Err(invalid());
+    }
+    Ok((width, height))
+}
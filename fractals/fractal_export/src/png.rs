@@ -0,0 +1,112 @@
+use std::io;
+use std::path::Path;
+
+use fractal_geometry::drawing::{Drawing, Rgb, Shape};
+use tiny_skia::{
+    Color, FillRule, Paint, Path as SkPath, PathBuilder, Pixmap, Rect, Stroke, Transform,
+};
+
+/// Largest image `render` will allocate, 1 GiB of RGBA pixels.
+pub const MAX_PIXELS: u64 = 1 << 28;
+
+/// Rasterizes a drawing on the CPU into a `width` x `height` image.
+///
+/// The drawing's view is scaled uniformly to fit the image and centered in it, so a
+/// drawing built for an 800x600 window still fills a 2048x2048 PNG horizontally.
+/// Sizes with nothing to draw or more than `MAX_PIXELS` pixels are errors.
+pub fn render(drawing: &Drawing, width: u32, height: u32) -> io::Result<Pixmap> {
+    // An allocation that fails aborts instead of returning `None`, so huge images are
+    // turned down before tiny-skia tries
+    let pixmap = (u64::from(width) * u64::from(height) <= MAX_PIXELS)
+        .then(|| Pixmap::new(width, height))
+        .flatten();
+    let mut pixmap = pixmap.ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("unsupported image size {width}x{height}"),
+        )
+    })?;
+    pixmap.fill(color(drawing.background));
+
+    // nannou puts the origin in the middle with y pointing up, the image puts it top left
+    // with y pointing down
+    let scale = (width as f32 / drawing.width).min(height as f32 / drawing.height);
+    let transform = Transform::from_row(
+        scale,
+        0.0,
+        0.0,
+        -scale,
+        width as f32 / 2.0,
+        height as f32 / 2.0,
+    );
+
+    let mut paint = Paint::default();
+    for (shape, style) in &drawing.shapes {
+        let Some(path) = path(shape) else {
+            continue; // nothing to draw, e.g. a polyline with a single point
+        };
+        if let Some(fill) = style.fill {
+            paint.set_color(color(fill));
+            pixmap.fill_path(&path, &paint, FillRule::Winding, transform, None);
+        }
+        if let Some(stroke) = style.stroke {
+            paint.set_color(color(stroke));
+            let stroke = Stroke {
+                width: style.stroke_weight,
+                ..Stroke::default()
+            };
+            pixmap.stroke_path(&path, &paint, &stroke, transform, None);
+        }
+    }
+    Ok(pixmap)
+}
+
+pub fn save(drawing: &Drawing, width: u32, height: u32, path: impl AsRef<Path>) -> io::Result<()> {
+    render(drawing, width, height)?
+        .save_png(path)
+        .map_err(io::Error::other)
+}
+
+fn path(shape: &Shape) -> Option<SkPath> {
+    let mut builder = PathBuilder::new();
+    match shape {
+        Shape::Polyline(points) => {
+            let (first, rest) = points.split_first()?;
+            builder.move_to(first.x, first.y);
+            for point in rest {
+                builder.line_to(point.x, point.y);
+            }
+        }
+        Shape::Line(start, end) => {
+            builder.move_to(start.x, start.y);
+            builder.line_to(end.x, end.y);
+        }
+        Shape::Tri([a, b, c]) => {
+            builder.move_to(a.x, a.y);
+            builder.line_to(b.x, b.y);
+            builder.line_to(c.x, c.y);
+            builder.close();
+        }
+        Shape::Polygon(points) => {
+            let (first, rest) = points.split_first()?;
+            builder.move_to(first.x, first.y);
+            for point in rest {
+                builder.line_to(point.x, point.y);
+            }
+            builder.close();
+        }
+        Shape::Circle { center, radius } => builder.push_circle(center.x, center.y, *radius),
+        Shape::Points { points, size } => {
+            for point in points {
+                let square =
+                    Rect::from_xywh(point.x - size / 2.0, point.y - size / 2.0, *size, *size)?;
+                builder.push_rect(square);
+            }
+        }
+    }
+    builder.finish()
+}
+
+fn color(color: Rgb) -> Color {
+    Color::from_rgba8(color.r, color.g, color.b, 255)
+}
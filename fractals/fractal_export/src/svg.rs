@@ -0,0 +1,120 @@
+use std::fmt::Write;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use fractal_geometry::drawing::{Drawing, Rgb, Shape, Style};
+use fractal_geometry::Point2;
+
+/// Renders a drawing as a standalone SVG document.
+pub fn to_string(drawing: &Drawing) -> String {
+    let mut svg = String::new();
+    let (width, height) = (drawing.width, drawing.height);
+
+    writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}">"#
+    )
+    .unwrap();
+    writeln!(
+        svg,
+        r#"<rect width="100%" height="100%" fill="{}"/>"#,
+        hex(drawing.background)
+    )
+    .unwrap();
+
+    for (shape, style) in &drawing.shapes {
+        let style = attributes(style);
+        match shape {
+            Shape::Polyline(points) => writeln!(
+                svg,
+                r#"<polyline points="{}" {style}/>"#,
+                point_list(drawing, points)
+            )
+            .unwrap(),
+            Shape::Line(start, end) => {
+                let (x1, y1) = to_svg(drawing, *start);
+                let (x2, y2) = to_svg(drawing, *end);
+                writeln!(
+                    svg,
+                    r#"<line x1="{x1}" y1="{y1}" x2="{x2}" y2="{y2}" {style}/>"#
+                )
+                .unwrap()
+            }
+            Shape::Tri(points) => writeln!(
+                svg,
+                r#"<polygon points="{}" {style}/>"#,
+                point_list(drawing, points)
+            )
+            .unwrap(),
+            Shape::Polygon(points) => writeln!(
+                svg,
+                r#"<polygon points="{}" {style}/>"#,
+                point_list(drawing, points)
+            )
+            .unwrap(),
+            Shape::Circle { center, radius } => {
+                let (cx, cy) = to_svg(drawing, *center);
+                writeln!(svg, r#"<circle cx="{cx}" cy="{cy}" r="{radius}" {style}/>"#).unwrap()
+            }
+            // One path of squares, far smaller than an element per point
+            Shape::Points { points, size } => {
+                let squares: String = points
+                    .iter()
+                    .map(|&point| {
+                        let (x, y) = to_svg(drawing, point);
+                        format!(
+                            "M{},{}h{size}v{size}h-{size}z",
+                            x - size / 2.0,
+                            y - size / 2.0
+                        )
+                    })
+                    .collect();
+                writeln!(svg, r#"<path d="{squares}" {style}/>"#).unwrap()
+            }
+        }
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+pub fn save(drawing: &Drawing, path: impl AsRef<Path>) -> io::Result<()> {
+    fs::write(path, to_string(drawing))
+}
+
+// nannou puts the origin in the middle with y pointing up, SVG puts it top left with y
+// pointing down
+fn to_svg(drawing: &Drawing, point: Point2) -> (f32, f32) {
+    (
+        point.x + drawing.width / 2.0,
+        drawing.height / 2.0 - point.y,
+    )
+}
+
+fn point_list(drawing: &Drawing, points: &[Point2]) -> String {
+    points
+        .iter()
+        .map(|&point| {
+            let (x, y) = to_svg(drawing, point);
+            format!("{x},{y}")
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn attributes(style: &Style) -> String {
+    let fill = style.fill.map_or("none".to_string(), hex);
+    match style.stroke {
+        Some(stroke) => format!(
+            r#"fill="{fill}" stroke="{}" stroke-width="{}""#,
+            hex(stroke),
+            style.stroke_weight
+        ),
+        None => format!(r#"fill="{fill}""#),
+    }
+}
+
+fn hex(color: Rgb) -> String {
+    format!("#{:02x}{:02x}{:02x}", color.r, color.g, color.b)
+}
@@ -0,0 +1,43 @@
+use std::path::PathBuf;
+
+use fractal_export::{png, Export};
+use fractal_geometry::drawing::{Drawing, WHITE};
+
+fn parse(args: &str) -> Result<Export, String> {
+    Export::parse_from(args.split_whitespace().map(String::from))
+}
+
+#[test]
+fn flags_are_found_among_other_arguments() {
+    let export = parse("sketch --scene s.toml --png out.png --size 400x300").unwrap();
+    assert_eq!(export.png, Some(PathBuf::from("out.png")));
+    assert_eq!(export.svg, None);
+    assert_eq!(export.size, Some((400, 300)));
+    assert!(!parse("sketch").unwrap().is_requested());
+}
+
+#[test]
+fn bad_flags_are_errors() {
+    assert_eq!(
+        parse("sketch --png").unwrap_err(),
+        "--png needs a path after it"
+    );
+    assert!(parse("sketch --svg --png out.png").is_err());
+    assert!(parse("sketch --png out.png --size 0x10")
+        .unwrap_err()
+        .contains("invalid size `0x10`"));
+    assert!(parse("sketch --size").is_err());
+}
+
+#[test]
+fn sizes_too_large_to_allocate_are_errors() {
+    let drawing = Drawing::new(800.0, 600.0, WHITE);
+    let path = std::env::temp_dir().join("fractal_export_huge.png");
+    let err = png::save(&drawing, 100_000, 100_000, &path).unwrap_err();
+    assert_eq!(err.to_string(), "unsupported image size 100000x100000");
+    assert!(!path.exists());
+    assert!(
+        png::render(&drawing, 1 << 30, 1).is_err(),
+        "wider than tiny-skia allows"
+    );
+}
@@ -0,0 +1,150 @@
+//! Rasterizes each sketch and compares it against a stored golden image.
+//!
+//! Golden images live in `tests/golden`. Run with `UPDATE_GOLDEN=1` to rewrite them
+//! after an intentional change to the art.
+
+use std::fs;
+use std::path::PathBuf;
+
+use fractal_export::png;
+use fractal_geometry::cantor::cantor;
+use fractal_geometry::circles::circles;
+use fractal_geometry::drawing::{Drawing, Shape, Style, BLACK, WHITE};
+use fractal_geometry::koch::{self, koch_line, KochParams, KochType, RadialLayout};
+use fractal_geometry::pt2;
+use fractal_geometry::sierpinski::sierpinski_triangles;
+use tiny_skia::Pixmap;
+
+const WIDTH: u32 = 256;
+const HEIGHT: u32 = 192;
+
+// A pixel only counts as changed when a channel moves by more than this, which absorbs
+// anti-aliasing noise
+const CHANNEL_TOLERANCE: u8 = 16;
+// Share of changed pixels allowed before the images are considered different
+const PIXEL_TOLERANCE: f32 = 0.005;
+
+fn assert_golden(name: &str, drawing: &Drawing) {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/golden")
+        .join(format!("{name}.png"));
+    let actual = png::render(drawing, WIDTH, HEIGHT).unwrap();
+
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        actual.save_png(&path).unwrap();
+        return;
+    }
+
+    let expected = Pixmap::load_png(&path)
+        .unwrap_or_else(|_| panic!("missing golden image {path:?}, run with UPDATE_GOLDEN=1"));
+    assert_eq!(
+        (actual.width(), actual.height()),
+        (expected.width(), expected.height()),
+        "{name}: image size changed"
+    );
+
+    let changed = actual
+        .pixels()
+        .iter()
+        .zip(expected.pixels())
+        .filter(|(a, e)| {
+            let channels =
+                |p: &tiny_skia::PremultipliedColorU8| [p.red(), p.green(), p.blue(), p.alpha()];
+            channels(a)
+                .iter()
+                .zip(channels(e))
+                .any(|(a, e)| a.abs_diff(e) > CHANNEL_TOLERANCE)
+        })
+        .count();
+    let share = changed as f32 / actual.pixels().len() as f32;
+    assert!(
+        share <= PIXEL_TOLERANCE,
+        "{name}: {:.2}% of pixels differ from the golden image",
+        share * 100.0
+    );
+}
+
+fn sketch_size() -> (f32, f32) {
+    (WIDTH as f32, HEIGHT as f32)
+}
+
+#[test]
+fn koch_linear() {
+    let (width, height) = sketch_size();
+    for depth in [1, 4] {
+        let mut drawing = Drawing::new(width, height, WHITE);
+        let points = koch_line(
+            pt2(-width / 2.0, 0.0),
+            pt2(width / 2.0, 0.0),
+            depth,
+            KochType::Linear,
+        );
+        drawing.push(Shape::Polyline(points), Style::stroke(BLACK, 1.0));
+        assert_golden(&format!("koch_linear_{depth}"), &drawing);
+    }
+}
+
+#[test]
+fn koch_radial() {
+    // Drawn at the sketch's 800x600 with the default radius of 300 and scaled down into
+    // the image
+    let mut drawing = Drawing::new(800.0, 600.0, WHITE);
+    let points = koch::koch_radial(&RadialLayout::new(12), 4, &KochParams::default()).concat();
+    drawing.push(Shape::Polyline(points), Style::stroke(BLACK, 4.0));
+    assert_golden("koch_radial_12_4", &drawing);
+}
+
+#[test]
+fn koch_line_radial() {
+    // Around the circle through `start` and `end`, left of the middle
+    let (width, height) = sketch_size();
+    let mut drawing = Drawing::new(width, height, WHITE);
+    let points = koch_line(
+        pt2(-width / 2.0, 0.0),
+        pt2(0.0, 0.0),
+        3,
+        KochType::Radial(6),
+    );
+    drawing.push(Shape::Polyline(points), Style::stroke(BLACK, 1.0));
+    assert_golden("koch_line_radial_6_3", &drawing);
+}
+
+#[test]
+fn sierpinski() {
+    let (width, height) = sketch_size();
+    for depth in [1, 3, 5] {
+        let mut drawing = Drawing::new(width, height, WHITE);
+        let start = pt2(-width / 2.0, -height / 2.0);
+        let end = pt2(width / 2.0, -height / 2.0);
+        let top = pt2(0.0, height);
+        for triangle in sierpinski_triangles(start, top, end, depth) {
+            drawing.push(Shape::Tri(triangle), Style::fill(BLACK));
+        }
+        assert_golden(&format!("sierpinski_{depth}"), &drawing);
+    }
+}
+
+#[test]
+fn cantor_set() {
+    let (width, height) = sketch_size();
+    let mut drawing = Drawing::new(width, height, WHITE);
+    for (start, end) in cantor(-(width / 2.0), -(height / 2.0) + 10.0, width) {
+        drawing.push(Shape::Line(start, end), Style::stroke(BLACK, 10.0));
+    }
+    assert_golden("cantor", &drawing);
+}
+
+#[test]
+fn circle_recursion() {
+    // Same layout as `first-try`, at its default 1024x768 window size
+    let mut drawing = Drawing::new(1024.0, 768.0, WHITE);
+    for circle in circles(pt2(1024.0 / 20.0, 768.0 / 20.0), 500.0) {
+        let shape = Shape::Circle {
+            center: circle.center,
+            radius: circle.radius,
+        };
+        drawing.push(shape, Style::stroke(BLACK, 2.0));
+    }
+    assert_golden("circles", &drawing);
+}
@@ -0,0 +1,73 @@
+//! Iterative Koch generation against the recursive implementation it replaced.
+//!
+//! Run with `cargo bench -p fractal_geometry`.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use fractal_geometry::koch::{koch_line, koch_line_into, KochParams, KochType};
+use fractal_geometry::{pt2, Point2};
+
+// The previous `koch_line` for `Radial`: one recursive call per segment, pushing onto a
+// vector that grows as it goes
+fn recursive(depth: u32, sides: u32) -> Vec<Point2> {
+    fn segment(points: &mut Vec<Point2>, start: Point2, end: Point2, depth: u32) {
+        if depth == 0 {
+            points.push(end);
+            return;
+        }
+        let t1 = start + (end - start) / 3.0;
+        let t2 = start + (end - start) * 2.0 / 3.0;
+        let (sin, cos) = 60f32.to_radians().sin_cos();
+        let v = t2 - t1;
+        let peak = t1 + pt2(v.x * cos - v.y * sin, v.x * sin + v.y * cos);
+        segment(points, start, t1, depth - 1);
+        segment(points, t1, peak, depth - 1);
+        segment(points, peak, t2, depth - 1);
+        segment(points, t2, end, depth - 1);
+    }
+
+    let vertex = |i: u32| {
+        let angle = i as f32 * std::f32::consts::TAU / sides as f32;
+        pt2(angle.cos(), angle.sin()) * 300.0
+    };
+    let mut points = vec![vertex(0)];
+    for i in 0..sides {
+        segment(&mut points, vertex(i), vertex(i + 1), depth);
+    }
+    points
+}
+
+fn radial(c: &mut Criterion) {
+    let (start, end) = (pt2(-300.0, 0.0), pt2(300.0, 0.0));
+    let params = KochParams::default();
+    let mut group = c.benchmark_group("radial_12");
+    group.sample_size(20);
+
+    for depth in [4, 6, 8] {
+        group.bench_with_input(BenchmarkId::new("recursive", depth), &depth, |b, &depth| {
+            b.iter(|| recursive(black_box(depth), 12))
+        });
+        group.bench_with_input(BenchmarkId::new("iterative", depth), &depth, |b, &depth| {
+            b.iter(|| koch_line(start, end, black_box(depth), KochType::Radial(12)))
+        });
+        // What the sketches do every frame: regenerate into the buffer of the last frame
+        let mut points = Vec::new();
+        group.bench_with_input(BenchmarkId::new("reused", depth), &depth, |b, &depth| {
+            b.iter(|| {
+                let koch_type = KochType::Radial(12);
+                koch_line_into(
+                    &mut points,
+                    start,
+                    end,
+                    black_box(depth),
+                    koch_type,
+                    &params,
+                );
+                points.len()
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, radial);
+criterion_main!(benches);
@@ -0,0 +1,245 @@
+use glam::DVec2;
+
+use crate::Point2;
+
+/// An Apollonian gasket grown from three mutually tangent circles.
+///
+/// Circles are given by their curvatures, one over the radius, with a negative curvature
+/// for a circle that encloses the others. Descartes' circle theorem gives the two circles
+/// touching all three, and its complex form, which treats every center as a complex
+/// number weighted by its curvature, tells where they are. Every later circle is the
+/// other solution of a quadruple it shares three circles with, which only takes
+/// additions: `k' = 2(k1 + k2 + k3) - k4`, and the same for curvature times center.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Apollonian {
+    pub curvatures: [f64; 3],
+}
+
+/// One circle of a gasket, fitted to where it was asked to be drawn.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GasketCircle {
+    pub center: Point2,
+    pub radius: f32,
+    pub curvature: f64,  // Relative to the enclosing circle, which has -1
+    pub generation: u32, // 0 for the starting circles and the two that touch all three
+}
+
+// A circle as Descartes' theorem sees it: curvature and curvature times center
+#[derive(Clone, Copy, Debug)]
+struct Bend {
+    k: f64,
+    kz: DVec2,
+}
+
+impl Bend {
+    fn center(self) -> DVec2 {
+        self.kz / self.k
+    }
+
+    fn radius(self) -> f64 {
+        1.0 / self.k
+    }
+
+    // The other circle touching `a`, `b` and `c`
+    fn flipped(self, [a, b, c]: [Bend; 3]) -> Bend {
+        Bend {
+            k: 2.0 * (a.k + b.k + c.k) - self.k,
+            kz: 2.0 * (a.kz + b.kz + c.kz) - self.kz,
+        }
+    }
+}
+
+impl Default for Apollonian {
+    // The classic gasket, two halves of the enclosing circle and everything between
+    fn default() -> Self {
+        Apollonian::new([-1.0, 2.0, 2.0])
+    }
+}
+
+impl Apollonian {
+    pub fn new(curvatures: [f64; 3]) -> Self {
+        Apollonian { curvatures }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        let [a, b, c] = self.curvatures;
+        if self.curvatures.iter().any(|k| !k.is_finite() || *k == 0.0) {
+            return Err(format!(
+                "curvatures must be numbers other than 0, which would be a straight line, \
+                 got {a}, {b}, {c}"
+            ));
+        }
+        if self.curvatures.iter().filter(|k| **k < 0.0).count() > 1 {
+            return Err(format!(
+                "only one circle can enclose the others, got curvatures {a}, {b}, {c}"
+            ));
+        }
+        if a * b + b * c + c * a < 0.0 {
+            return Err(format!(
+                "circles with curvatures {a}, {b} and {c} cannot all touch each other"
+            ));
+        }
+        if self.curvatures.iter().all(|k| *k > 0.0) && self.outer_curvature() >= -1e-9 {
+            return Err(format!(
+                "no circle encloses circles with curvatures {a}, {b} and {c}, make one of \
+                 them negative to start inside it"
+            ));
+        }
+        Ok(())
+    }
+
+    // The smaller solution of Descartes' theorem, negative when it encloses the triple
+    fn outer_curvature(&self) -> f64 {
+        let [a, b, c] = self.curvatures;
+        a + b + c - 2.0 * (a * b + b * c + c * a).max(0.0).sqrt()
+    }
+
+    /// Circles for at most `depth` generations after the first five, scaled and moved so
+    /// the enclosing circle has `radius` around `center` and comes first. Circles with a
+    /// radius below `min_radius` are left out, together with everything inside them.
+    pub fn circles(
+        &self,
+        center: Point2,
+        radius: f32,
+        depth: u32,
+        min_radius: f32,
+    ) -> Vec<GasketCircle> {
+        let triple = self.starting_triple();
+        let inner = self.fourth(triple);
+        let outer = inner.flipped(triple);
+        let mut starting = vec![triple[0], triple[1], triple[2], inner, outer];
+        // `validate` makes sure one of them is negative
+        let enclosing = starting.iter().position(|bend| bend.k < 0.0).unwrap_or(4);
+        let enclosing = starting.remove(enclosing);
+
+        let unit = -enclosing.k;
+        let gasket = Gasket {
+            origin: enclosing.center(),
+            scale: f64::from(radius) * unit,
+            unit,
+            center: center.as_f64(),
+            min_radius: f64::from(min_radius),
+            depth,
+        };
+        // The enclosing circle comes first so that filled circles stack up inwards
+        let mut circles = vec![gasket.circle(enclosing, 0)];
+        for bend in starting {
+            if gasket.keeps(bend) {
+                circles.push(gasket.circle(bend, 0));
+            }
+        }
+        for fourth in [inner, outer] {
+            let [a, b, c] = triple;
+            gasket.fill(&mut circles, [a, b, c, fourth], 1);
+        }
+        circles
+    }
+
+    // The triple touching in a row: the first circle at the origin and the second on the
+    // x axis, the third above them
+    fn starting_triple(&self) -> [Bend; 3] {
+        let [a, b, c] = self.curvatures;
+        // Signed radii add up to the distance between tangent centers, inside or out
+        let distance = |k1: f64, k2: f64| (1.0 / k1 + 1.0 / k2).abs();
+        let (ab, ac, bc) = (distance(a, b), distance(a, c), distance(b, c));
+        let x = (ac * ac - bc * bc + ab * ab) / (2.0 * ab);
+        let y = (ac * ac - x * x).max(0.0).sqrt();
+        let bend = |k: f64, z: DVec2| Bend { k, kz: z * k };
+        [
+            bend(a, DVec2::ZERO),
+            bend(b, DVec2::new(ab, 0.0)),
+            bend(c, DVec2::new(x, y)),
+        ]
+    }
+
+    // The larger solution of Descartes' theorem with the center that really touches all
+    // three, out of the two its complex form allows
+    fn fourth(&self, triple: [Bend; 3]) -> Bend {
+        let [a, b, c] = triple;
+        let k = a.k + b.k + c.k + 2.0 * (a.k * b.k + b.k * c.k + c.k * a.k).max(0.0).sqrt();
+        let root = complex_sqrt(
+            complex_mul(a.kz, b.kz) + complex_mul(b.kz, c.kz) + complex_mul(c.kz, a.kz),
+        );
+        let miss = |bend: Bend| -> f64 {
+            triple
+                .iter()
+                .map(|other| {
+                    let touching = (bend.radius() + other.radius()).abs();
+                    (bend.center().distance(other.center()) - touching).abs()
+                })
+                .sum()
+        };
+        let sum = a.kz + b.kz + c.kz;
+        let plus = Bend {
+            k,
+            kz: sum + 2.0 * root,
+        };
+        let minus = Bend {
+            k,
+            kz: sum - 2.0 * root,
+        };
+        if miss(plus) <= miss(minus) {
+            plus
+        } else {
+            minus
+        }
+    }
+}
+
+// Where the generated circles go and which of them are worth generating
+struct Gasket {
+    origin: DVec2, // Center of the enclosing circle before fitting
+    scale: f64,
+    unit: f64, // Curvature of the enclosing circle, negated
+    center: DVec2,
+    min_radius: f64,
+    depth: u32,
+}
+
+impl Gasket {
+    fn circle(&self, bend: Bend, generation: u32) -> GasketCircle {
+        GasketCircle {
+            center: ((bend.center() - self.origin) * self.scale + self.center).as_f32(),
+            radius: (bend.radius().abs() * self.scale) as f32,
+            curvature: bend.k / self.unit,
+            generation,
+        }
+    }
+
+    fn keeps(&self, bend: Bend) -> bool {
+        bend.radius().abs() * self.scale >= self.min_radius
+    }
+
+    // Swaps each of the first three circles for the other circle touching the remaining
+    // three, the newest one among them, and goes on from there
+    fn fill(&self, circles: &mut Vec<GasketCircle>, quadruple: [Bend; 4], generation: u32) {
+        if generation > self.depth {
+            return;
+        }
+        let [a, b, c, newest] = quadruple;
+        for (old, others) in [
+            (a, [b, c, newest]),
+            (b, [a, c, newest]),
+            (c, [a, b, newest]),
+        ] {
+            let new = old.flipped(others);
+            if self.keeps(new) {
+                circles.push(self.circle(new, generation));
+                let [a, b, c] = others;
+                self.fill(circles, [a, b, c, new], generation + 1);
+            }
+        }
+    }
+}
+
+fn complex_mul(a: DVec2, b: DVec2) -> DVec2 {
+    DVec2::new(a.x * b.x - a.y * b.y, a.x * b.y + a.y * b.x)
+}
+
+// The root with a non-negative real part
+fn complex_sqrt(z: DVec2) -> DVec2 {
+    let length = z.length();
+    let re = ((length + z.x) / 2.0).max(0.0).sqrt();
+    let im = ((length - z.x) / 2.0).max(0.0).sqrt();
+    DVec2::new(re, if z.y < 0.0 { -im } else { im })
+}
@@ -0,0 +1,233 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::mem::size_of;
+
+use crate::apollonian::GasketCircle;
+use crate::cantor::CantorRule;
+use crate::circles::Branching;
+use crate::koch::RadialLayout;
+use crate::lsystem::LSystem;
+use crate::sierpinski::SierpinskiShape;
+use crate::Point2;
+
+/// What generating a curve would cost, worked out without generating it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Estimate {
+    pub segments: u64,
+    pub vertices: u64,
+    pub bytes: u64, // Memory held by the generated points and anything built on the way
+}
+
+impl Estimate {
+    /// A straight Koch curve.
+    pub fn koch_linear(depth: u32) -> Self {
+        Estimate::polyline(4u64.saturating_pow(depth), 1)
+    }
+
+    /// A radial Koch shape, including per-side depths and star polygons with several loops.
+    pub fn koch_radial(layout: &RadialLayout, depth: u32) -> Self {
+        let segments = (0..layout.sides as usize)
+            .map(|side| 4u64.saturating_pow(layout.depth(side, depth)))
+            .fold(0u64, u64::saturating_add);
+        Estimate::polyline(segments, u64::from(layout.loops()))
+    }
+
+    /// Every row of a Cantor set down to `depth` splits, one segment per interval.
+    pub fn cantor(rule: &CantorRule, depth: u32) -> Self {
+        let copies = u64::from(rule.copies());
+        let segments = (0..=depth)
+            .map(|split| copies.saturating_pow(split))
+            .fold(0u64, u64::saturating_add);
+        Estimate::polyline(segments, segments)
+    }
+
+    /// The 2D Cantor dust after `depth` splits, counting the sides of its squares.
+    pub fn cantor_dust(rule: &CantorRule, depth: u32) -> Self {
+        let squares = u64::from(rule.copies()).saturating_pow(depth.saturating_mul(2));
+        let vertices = squares.saturating_mul(4);
+        Estimate {
+            segments: vertices,
+            vertices,
+            bytes: bytes(vertices, size_of::<Point2>()),
+        }
+    }
+
+    /// A member of the Sierpinski family split `depth` times, counting polygon sides.
+    pub fn sierpinski(shape: SierpinskiShape, depth: u32) -> Self {
+        let polygons = u64::from(shape.copies()).saturating_pow(depth);
+        let vertices = polygons.saturating_mul(u64::from(shape.sides()));
+        Estimate {
+            segments: vertices,
+            vertices,
+            bytes: bytes(vertices, size_of::<Point2>()),
+        }
+    }
+
+    /// A recursive shape starting at `radius`, counting polygon sides and one segment for
+    /// every circle.
+    pub fn branching(branching: &Branching, radius: f32, depth: u32) -> Self {
+        let children = branching.children() as u64;
+        let shapes = (0..=branching.levels(radius, depth))
+            .map(|level| children.saturating_pow(level))
+            .fold(0u64, u64::saturating_add);
+        let vertices = shapes.saturating_mul(u64::from(branching.shape.sides()));
+        Estimate {
+            segments: vertices,
+            vertices,
+            bytes: bytes(vertices, size_of::<Point2>()),
+        }
+    }
+
+    /// An Apollonian gasket `depth` generations after its first five circles, one segment
+    /// per circle. Three new circles fill the gaps around every circle, so this is an
+    /// upper bound for gaskets whose smallest circles get left out.
+    pub fn apollonian(depth: u32) -> Self {
+        let circles = 3u64
+            .saturating_pow(depth.saturating_add(1))
+            .saturating_add(2);
+        Estimate {
+            segments: circles,
+            vertices: circles,
+            bytes: bytes(circles, size_of::<GasketCircle>()),
+        }
+    }
+
+    /// An L-system expanded `iterations` times.
+    ///
+    /// Stochastic and context-sensitive rules are counted as if the longest replacement
+    /// always applied, so this is an upper bound rather than an exact count.
+    pub fn lsystem(lsystem: &LSystem, iterations: u32) -> Self {
+        let mut counts: BTreeMap<char, u64> = BTreeMap::new();
+        for symbol in lsystem.axiom.chars() {
+            *counts.entry(symbol).or_default() += 1;
+        }
+        for _ in 0..iterations {
+            let mut next = BTreeMap::new();
+            for (&symbol, &count) in &counts {
+                for (produced, times) in worst_replacement(lsystem, symbol) {
+                    let added = count.saturating_mul(times);
+                    let total: &mut u64 = next.entry(produced).or_default();
+                    *total = total.saturating_add(added);
+                }
+            }
+            counts = next;
+        }
+
+        let count = |symbol| counts.get(&symbol).copied().unwrap_or(0);
+        let symbols = counts.values().fold(0u64, |sum, &n| sum.saturating_add(n));
+        let segments = count('F').saturating_add(count('G'));
+        // Every `]` and `f` can start a new stroke with its own first point
+        let strokes = 1u64.saturating_add(count(']')).saturating_add(count('f'));
+
+        let mut estimate = Estimate::polyline(segments, strokes);
+        estimate.vertices = estimate.vertices.min(segments.saturating_mul(2));
+        estimate.bytes = bytes(estimate.vertices, size_of::<Point2>())
+            .saturating_add(bytes(symbols, size_of::<char>()));
+        estimate
+    }
+
+    // `segments` split over `strokes` polylines, each with one extra point to start on
+    fn polyline(segments: u64, strokes: u64) -> Self {
+        let vertices = segments.saturating_add(strokes);
+        Estimate {
+            segments,
+            vertices,
+            bytes: bytes(vertices, size_of::<Point2>()),
+        }
+    }
+}
+
+impl fmt::Display for Estimate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} segments, {} vertices, {}",
+            self.segments,
+            self.vertices,
+            human_bytes(self.bytes)
+        )
+    }
+}
+
+// Replacement of `symbol` with the most of every symbol any of its rules can produce
+fn worst_replacement(lsystem: &LSystem, symbol: char) -> BTreeMap<char, u64> {
+    let rules: Vec<_> = lsystem
+        .rules
+        .iter()
+        .filter(|r| r.symbol == symbol)
+        .collect();
+    let mut worst = BTreeMap::new();
+    // Without a context-free rule some occurrences may match nothing and stay as they are
+    if !rules.iter().any(|r| r.left.is_none() && r.right.is_none()) {
+        worst.insert(symbol, 1);
+    }
+    for rule in rules {
+        let mut produced: BTreeMap<char, u64> = BTreeMap::new();
+        for symbol in rule.replacement.chars() {
+            *produced.entry(symbol).or_default() += 1;
+        }
+        for (symbol, times) in produced {
+            let most = worst.entry(symbol).or_default();
+            *most = times.max(*most);
+        }
+    }
+    worst
+}
+
+fn bytes(count: u64, size: usize) -> u64 {
+    count.saturating_mul(size as u64)
+}
+
+fn human_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["KiB", "MiB", "GiB", "TiB", "PiB"];
+    if bytes < 1024 {
+        return format!("{bytes} bytes");
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{size:.1} {}", UNITS[unit])
+}
+
+/// Upper limit on how much a single curve may draw.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Budget {
+    pub max_segments: u64,
+}
+
+impl Budget {
+    /// About a second of generation and 40 MiB of points, and still smooth to draw.
+    pub const DEFAULT_MAX_SEGMENTS: u64 = 5_000_000;
+
+    pub fn new(max_segments: u64) -> Self {
+        Budget { max_segments }
+    }
+
+    pub fn check(&self, estimate: &Estimate) -> Result<(), String> {
+        if estimate.segments > self.max_segments {
+            return Err(format!(
+                "this would draw {estimate}, over the limit of {} segments",
+                self.max_segments
+            ));
+        }
+        Ok(())
+    }
+
+    /// Deepest depth up to `depth` whose estimate fits the budget, if even depth 0 does.
+    ///
+    /// Counts up from 0, so asking for a huge depth costs no more than the depths that fit.
+    pub fn cap(&self, depth: u32, estimate: impl Fn(u32) -> Estimate) -> Option<u32> {
+        (0..=depth)
+            .take_while(|&depth| self.check(&estimate(depth)).is_ok())
+            .last()
+    }
+}
+
+impl Default for Budget {
+    fn default() -> Self {
+        Budget::new(Budget::DEFAULT_MAX_SEGMENTS)
+    }
+}
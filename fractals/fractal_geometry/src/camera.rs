@@ -0,0 +1,82 @@
+use glam::DVec2;
+
+use crate::Point2;
+
+/// Where a window looks at a fractal from.
+///
+/// Fractals are generated in world coordinates, which match the window's at a scale of
+/// one. The camera puts `center` in the middle of the window, scales by `scale` and turns
+/// by `rotation` degrees counter-clockwise. World coordinates are `f64` so that deep zooms
+/// into a curve still have precision to spare when the result is handed to the window
+/// as `f32`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Camera {
+    pub center: DVec2,
+    pub scale: f64,
+    pub rotation: f64,
+}
+
+impl Default for Camera {
+    fn default() -> Self {
+        Camera {
+            center: DVec2::ZERO,
+            scale: 1.0,
+            rotation: 0.0,
+        }
+    }
+}
+
+impl Camera {
+    /// The deepest zoom the camera allows, around where `f64` runs out of digits for
+    /// a curve the size of a window.
+    pub const MAX_SCALE: f64 = 1e12;
+
+    pub fn to_screen(&self, world: DVec2) -> Point2 {
+        rotate(
+            (world - self.center) * self.scale,
+            self.rotation.to_radians(),
+        )
+        .as_f32()
+    }
+
+    pub fn to_world(&self, screen: Point2) -> DVec2 {
+        rotate(screen.as_f64(), -self.rotation.to_radians()) / self.scale + self.center
+    }
+
+    /// Zoomed in by `factor`, or out for a factor below 1, keeping whatever is at `screen`
+    /// in place, e.g. the point under the mouse.
+    pub fn zoomed_about(&self, factor: f64, screen: Point2) -> Camera {
+        let fixed = self.to_world(screen);
+        Camera {
+            center: fixed + (self.center - fixed) / factor,
+            scale: self.scale * factor,
+            ..*self
+        }
+    }
+
+    /// Moved so that the fractal follows a drag of `screen` across the window.
+    pub fn panned(&self, screen: Point2) -> Camera {
+        let moved = rotate(screen.as_f64(), -self.rotation.to_radians()) / self.scale;
+        Camera {
+            center: self.center - moved,
+            ..*self
+        }
+    }
+
+    /// Whether any of the disc around `world` with a `radius` in world units shows up in a
+    /// `width` x `height` window.
+    pub fn sees(&self, world: DVec2, radius: f64, width: f32, height: f32) -> bool {
+        let offset = rotate(
+            (world - self.center) * self.scale,
+            self.rotation.to_radians(),
+        );
+        let radius = radius * self.scale;
+        offset.x.abs() <= f64::from(width) / 2.0 + radius
+            && offset.y.abs() <= f64::from(height) / 2.0 + radius
+    }
+}
+
+fn rotate(v: DVec2, radians: f64) -> DVec2 {
+    let (sin, cos) = radians.sin_cos();
+    DVec2::new(v.x * cos - v.y * sin, v.x * sin + v.y * cos)
+}
@@ -0,0 +1,260 @@
+use std::fmt;
+use std::str::FromStr;
+
+use glam::DVec2;
+
+use crate::{pt2, Point2};
+
+/// Bars of a Cantor set as `(start, end)` segments, one row every 30 units going up from
+/// `y`, until the bars get shorter than a third of a unit.
+pub fn cantor(x: f32, y: f32, len: f32) -> Vec<(Point2, Point2)> {
+    let mut segments = Vec::new();
+    cantor_row(&mut segments, x, y, len);
+    segments
+}
+
+fn cantor_row(segments: &mut Vec<(Point2, Point2)>, x: f32, mut y: f32, len: f32) {
+    if len < 0.33 {
+        // break infinite recursive loop
+        return;
+    }
+
+    segments.push((pt2(x, y), pt2(x + len, y)));
+
+    y += 30.0; // space between rows
+
+    cantor_row(segments, x, y, len / 3.0);
+    cantor_row(segments, x + (len * (2.0 / 3.0)), y, len / 3.0);
+}
+
+/// Which parts of a split interval a Cantor set keeps, e.g. `101` for the classic set
+/// or `10101` for one that keeps three fifths.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Mask(pub Vec<bool>);
+
+impl Mask {
+    pub fn parts(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn kept(&self) -> usize {
+        self.0.iter().filter(|keep| **keep).count()
+    }
+}
+
+impl Default for Mask {
+    fn default() -> Self {
+        Mask(vec![true, false, true])
+    }
+}
+
+impl FromStr for Mask {
+    type Err = String;
+
+    // Ones and zeros, at least two of them
+    fn from_str(mask: &str) -> Result<Self, Self::Err> {
+        let keep = mask
+            .chars()
+            .map(|digit| match digit {
+                '1' => Ok(true),
+                '0' => Ok(false),
+                _ => Err(format!(
+                    "invalid mask `{mask}`, expected ones and zeros like 101"
+                )),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if keep.len() < 2 {
+            return Err(format!("a mask needs at least two parts, got `{mask}`"));
+        }
+        Ok(Mask(keep))
+    }
+}
+
+impl fmt::Display for Mask {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for keep in &self.0 {
+            f.write_str(if *keep { "1" } else { "0" })?;
+        }
+        Ok(())
+    }
+}
+
+/// How every interval of a Cantor set splits into the next row's intervals.
+#[derive(Clone, Debug, PartialEq)]
+pub enum CantorRule {
+    /// Split into one part per digit of `mask` and keep the ones marked 1. The parts are
+    /// as wide as `widths` says (normalized, so `[1, 2, 1]` and `[0.25, 0.5, 0.25]` are
+    /// the same), or all equal when it is empty.
+    Mask { mask: Mask, widths: Vec<f32> },
+    /// The fat Cantor set of Smith, Volterra and Cantor: split `n` takes a gap of `1/4^n`
+    /// out of the middle of every interval, which leaves half of the length behind
+    /// however deep it goes.
+    SmithVolterra,
+}
+
+impl Default for CantorRule {
+    fn default() -> Self {
+        CantorRule::Mask {
+            mask: Mask::default(),
+            widths: Vec::new(),
+        }
+    }
+}
+
+impl CantorRule {
+    pub fn validate(&self) -> Result<(), String> {
+        if let CantorRule::Mask { mask, widths } = self {
+            if mask.parts() < 2 || mask.kept() == 0 {
+                return Err(format!(
+                    "a mask needs at least two parts and one to keep, got `{mask}`"
+                ));
+            }
+            if !widths.is_empty() && widths.len() != mask.parts() {
+                return Err(format!(
+                    "mask `{mask}` has {} parts but got {} widths",
+                    mask.parts(),
+                    widths.len()
+                ));
+            }
+            if widths
+                .iter()
+                .any(|width| !(width.is_finite() && *width > 0.0))
+            {
+                return Err(format!("widths must be positive, got {widths:?}"));
+            }
+        }
+        Ok(())
+    }
+
+    /// How many intervals every interval splits into.
+    pub fn copies(&self) -> u32 {
+        match self {
+            CantorRule::Mask { mask, .. } => mask.kept() as u32,
+            CantorRule::SmithVolterra => 2,
+        }
+    }
+
+    /// The intervals left of `0..1` after no split, one split and so on up to `depth`
+    /// splits, one row each, as `(start, end)` from left to right.
+    pub fn rows(&self, depth: u32) -> Vec<Vec<(f64, f64)>> {
+        let mut rows = vec![vec![(0.0, 1.0)]];
+        for split in 1..=depth {
+            let last = rows.last().unwrap();
+            let mut row = Vec::with_capacity(last.len() * self.copies() as usize);
+            for &interval in last {
+                self.split(&mut row, interval, split);
+            }
+            rows.push(row);
+        }
+        rows
+    }
+
+    /// The 2D Cantor dust: every pair of the intervals left after `depth` splits, as the
+    /// bottom left and top right corners of squares in the unit square.
+    pub fn dust(&self, depth: u32) -> Vec<(DVec2, DVec2)> {
+        let row = self.rows(depth).pop().unwrap();
+        row.iter()
+            .flat_map(|&(bottom, top)| {
+                row.iter()
+                    .map(move |&(left, right)| (DVec2::new(left, bottom), DVec2::new(right, top)))
+            })
+            .collect()
+    }
+
+    // Adds the parts of `interval` kept by split number `split`, counting from 1
+    fn split(&self, row: &mut Vec<(f64, f64)>, (start, end): (f64, f64), split: u32) {
+        let len = end - start;
+        match self {
+            CantorRule::Mask { mask, widths } => {
+                let total: f64 = if widths.is_empty() {
+                    mask.parts() as f64
+                } else {
+                    widths.iter().map(|width| f64::from(*width)).sum()
+                };
+                let width = |part: usize| widths.get(part).map_or(1.0, |width| f64::from(*width));
+                let mut left = start;
+                for (part, keep) in mask.0.iter().enumerate() {
+                    let right = left + len * width(part) / total;
+                    if *keep {
+                        row.push((left, right));
+                    }
+                    left = right;
+                }
+            }
+            CantorRule::SmithVolterra => {
+                let middle = (start + end) / 2.0;
+                let gap = 0.25f64.powi(split as i32);
+                row.push((start, middle - gap / 2.0));
+                row.push((middle + gap / 2.0, end));
+            }
+        }
+    }
+}
+
+/// How much of a row of a Cantor set is left.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Measure {
+    pub segments: usize,
+    pub length: f64, // Total length of the intervals, out of 1 for the first row
+}
+
+impl Measure {
+    pub fn of(row: &[(f64, f64)]) -> Self {
+        Measure {
+            segments: row.len(),
+            length: row.iter().map(|(start, end)| end - start).sum(),
+        }
+    }
+}
+
+/// The dimension `log copies / log scale` read off the rows of a Cantor set: after
+/// `depth` splits there are `copies^depth` intervals, on average `1 / scale^depth` long.
+///
+/// Exact for masks with equal widths, e.g. `log 2 / log 3` for `101`, and an estimate
+/// that gets better with depth for the others.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Dimension {
+    pub copies: f64,
+    pub scale: f64,
+}
+
+impl Dimension {
+    /// `None` for rows with no split or nothing left to measure.
+    pub fn of(rows: &[Vec<(f64, f64)>]) -> Option<Self> {
+        let depth = rows.len().checked_sub(1).filter(|depth| *depth > 0)? as f64;
+        let last = Measure::of(rows.last()?);
+        if last.length <= 0.0 {
+            return None;
+        }
+        let mean = last.length / last.segments as f64;
+        Some(Dimension {
+            copies: (last.segments as f64).powf(1.0 / depth),
+            scale: (1.0 / mean).powf(1.0 / depth),
+        })
+    }
+
+    pub fn value(&self) -> f64 {
+        self.copies.ln() / self.scale.ln()
+    }
+}
+
+// e.g. `log 2 / log 3 = 0.6309`
+impl fmt::Display for Dimension {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Whole numbers lose the digits that float arithmetic leaves on them
+        let number = |x: f64| {
+            if (x - x.round()).abs() < 1e-6 {
+                format!("{}", x.round())
+            } else {
+                format!("{x:.3}")
+            }
+        };
+        write!(
+            f,
+            "log {} / log {} = {:.4}",
+            number(self.copies),
+            number(self.scale),
+            self.value()
+        )
+    }
+}
@@ -0,0 +1,268 @@
+use std::f32::consts::{SQRT_2, TAU};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{pt2, rotate, Point2};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Circle {
+    pub center: Point2,
+    pub radius: f32,
+}
+
+/// Recursive circles: every circle larger than 8 units spawns four half-size children,
+/// offset by half its radius to the left, right, top and bottom.
+pub fn circles(center: Point2, radius: f32) -> Vec<Circle> {
+    BranchPreset::Four
+        .branching()
+        .branches(center, radius, u32::MAX)
+        .into_iter()
+        .map(|branch| Circle {
+            center: branch.center,
+            radius: branch.radius,
+        })
+        .collect()
+}
+
+/// What every copy of a recursive shape is drawn as, each one inside a circle of the
+/// copy's radius.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BranchShape {
+    Circle,
+    Square,       // Sides along the axes until rotated
+    Polygon(u32), // A corner pointing up until rotated
+}
+
+impl BranchShape {
+    /// The corners of `branch` in this shape, `None` for circles.
+    pub fn outline(self, branch: &Branch) -> Option<Vec<Point2>> {
+        let (sides, first) = match self {
+            BranchShape::Circle => return None,
+            BranchShape::Square => (4, 45f32),
+            BranchShape::Polygon(sides) => (sides, 90.0),
+        };
+        let corners = (0..sides)
+            .map(|k| {
+                let angle = first.to_radians() + branch.turn + k as f32 * TAU / sides as f32;
+                branch.center + rotate(pt2(branch.radius, 0.0), angle)
+            })
+            .collect();
+        Some(corners)
+    }
+
+    pub fn sides(self) -> u32 {
+        match self {
+            BranchShape::Circle => 1,
+            BranchShape::Square => 4,
+            BranchShape::Polygon(sides) => sides,
+        }
+    }
+}
+
+/// How a recursive shape branches out.
+///
+/// Every shape larger than `min_radius` and less than the depth limit deep spawns one
+/// child per angle, `distance` of its radius away from its center and `ratio` times its
+/// size, plus one in its middle with `center`. Angles are in degrees counterclockwise
+/// from the right, and every level turns `rotation` degrees further than its parent,
+/// children's placement included.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Branching {
+    pub angles: Vec<f32>,
+    pub center: bool,
+    pub distance: f32,
+    pub ratio: f32,
+    pub rotation: f32,
+    pub shape: BranchShape,
+    pub min_radius: f32,
+}
+
+/// One copy in a recursive shape, `level` 0 being the largest.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Branch {
+    pub center: Point2,
+    pub radius: f32,
+    pub turn: f32, // Radians
+    pub level: u32,
+}
+
+impl Branching {
+    /// `children` children spread evenly around their parent, the first one to the right.
+    pub fn evenly(children: u32) -> Vec<f32> {
+        (0..children)
+            .map(|child| child as f32 * 360.0 / children as f32)
+            .collect()
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.angles.is_empty() && !self.center {
+            return Err("a recursive shape needs at least one child".to_string());
+        }
+        if let Some(angle) = self.angles.iter().find(|angle| !angle.is_finite()) {
+            return Err(format!("child angles must be numbers, got {angle}"));
+        }
+        if !(self.ratio > 0.0 && self.ratio < 1.0) {
+            return Err(format!(
+                "ratio must be between 0 and 1 so children shrink, got {}",
+                self.ratio
+            ));
+        }
+        if !(self.distance >= 0.0 && self.distance.is_finite()) {
+            return Err(format!(
+                "distance must not be negative, got {}",
+                self.distance
+            ));
+        }
+        if !self.rotation.is_finite() {
+            return Err(format!("rotation must be a number, got {}", self.rotation));
+        }
+        if !(self.min_radius > 0.0 && self.min_radius.is_finite()) {
+            return Err(format!(
+                "the smallest radius must be positive, got {}",
+                self.min_radius
+            ));
+        }
+        match self.shape {
+            BranchShape::Polygon(sides) if sides < 3 => {
+                Err(format!("a polygon needs at least 3 sides, got {sides}"))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Children of every shape that branches.
+    pub fn children(&self) -> usize {
+        self.angles.len() + usize::from(self.center)
+    }
+
+    /// How many levels below the first one a shape of `radius` branches into, at most `depth`.
+    pub fn levels(&self, radius: f32, depth: u32) -> u32 {
+        let mut levels = 0;
+        let mut radius = radius;
+        while levels < depth && radius > self.min_radius {
+            radius *= self.ratio;
+            levels += 1;
+        }
+        levels
+    }
+
+    /// Every shape down to `depth` levels below the one at `center`, parents before
+    /// their children.
+    pub fn branches(&self, center: Point2, radius: f32, depth: u32) -> Vec<Branch> {
+        let mut branches = Vec::new();
+        let first = Branch {
+            center,
+            radius,
+            turn: 0.0,
+            level: 0,
+        };
+        self.branch(&mut branches, first, depth);
+        branches
+    }
+
+    fn branch(&self, branches: &mut Vec<Branch>, parent: Branch, depth: u32) {
+        branches.push(parent);
+        if parent.level >= depth || parent.radius <= self.min_radius {
+            return;
+        }
+        let child = |center| Branch {
+            center,
+            radius: parent.radius * self.ratio,
+            turn: parent.turn + self.rotation.to_radians(),
+            level: parent.level + 1,
+        };
+        if self.center {
+            self.branch(branches, child(parent.center), depth);
+        }
+        let offset = pt2(parent.radius * self.distance, 0.0);
+        for angle in &self.angles {
+            let center = parent.center + rotate(offset, parent.turn + angle.to_radians());
+            self.branch(branches, child(center), depth);
+        }
+    }
+}
+
+/// Ready-made rules for `Branching`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BranchPreset {
+    Four,       // `first-try`'s circles, four half-size children along the axes
+    Two,        // Its commented out variant, two children to the left and right
+    Apollonian, // Three tangent circles inside every circle
+    Vicsek,     // Squares with a third-size copy in the middle and at every side
+}
+
+impl BranchPreset {
+    pub const ALL: [BranchPreset; 4] = [
+        BranchPreset::Four,
+        BranchPreset::Two,
+        BranchPreset::Apollonian,
+        BranchPreset::Vicsek,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            BranchPreset::Four => "four",
+            BranchPreset::Two => "two",
+            BranchPreset::Apollonian => "apollonian",
+            BranchPreset::Vicsek => "vicsek",
+        }
+    }
+
+    pub fn branching(self) -> Branching {
+        let halves = |angles: &[f32], min_radius| Branching {
+            angles: angles.to_vec(),
+            center: false,
+            distance: 0.5,
+            ratio: 0.5,
+            rotation: 0.0,
+            shape: BranchShape::Circle,
+            min_radius,
+        };
+        match self {
+            BranchPreset::Four => halves(&[0.0, 180.0, 90.0, 270.0], 8.0),
+            BranchPreset::Two => halves(&[0.0, 180.0], 2.0),
+            BranchPreset::Apollonian => {
+                // Three equal circles touching each other and their parent
+                let ratio = 2.0 * 3f32.sqrt() - 3.0;
+                Branching {
+                    angles: vec![90.0, 210.0, 330.0],
+                    distance: 1.0 - ratio,
+                    ratio,
+                    ..halves(&[], 2.0)
+                }
+            }
+            BranchPreset::Vicsek => Branching {
+                angles: Branching::evenly(4),
+                center: true,
+                // Two thirds of the half-width, which is the radius over the square root of 2
+                distance: SQRT_2 / 3.0,
+                ratio: 1.0 / 3.0,
+                shape: BranchShape::Square,
+                ..halves(&[], 2.0)
+            },
+        }
+    }
+}
+
+impl fmt::Display for BranchPreset {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for BranchPreset {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        BranchPreset::ALL
+            .into_iter()
+            .find(|preset| preset.name() == name)
+            .ok_or_else(|| {
+                let names: Vec<_> = BranchPreset::ALL.iter().map(|p| p.name()).collect();
+                format!(
+                    "unknown preset `{name}`, options include: {}",
+                    names.join(", ")
+                )
+            })
+    }
+}
@@ -0,0 +1,265 @@
+use std::collections::HashSet;
+use std::f64::consts::TAU;
+use std::fmt;
+
+use glam::DVec2;
+
+use crate::cantor::Dimension;
+use crate::drawing::Shape;
+use crate::Point2;
+
+/// A box-counting estimate of fractal dimension.
+///
+/// Lays grids of ever smaller boxes over some geometry and counts the boxes it touches.
+/// For a fractal the count grows like `(1 / size)^D`, so `D` is the slope of the line
+/// through `log boxes` against `log (1 / size)`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BoxCounting {
+    pub counts: Vec<(f64, usize)>, // Box size and how many boxes were touched
+    pub fit: Fit,
+}
+
+/// Least-squares line `y = slope * x + intercept`, and how much of `y` it explains.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Fit {
+    pub slope: f64,
+    pub intercept: f64,
+    pub r_squared: f64,
+}
+
+impl Fit {
+    /// `None` without two different `x`.
+    pub fn of(points: &[(f64, f64)]) -> Option<Self> {
+        let n = points.len() as f64;
+        let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / n;
+        let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / n;
+        let (mut xx, mut xy, mut yy) = (0.0, 0.0, 0.0);
+        for (x, y) in points {
+            xx += (x - mean_x) * (x - mean_x);
+            xy += (x - mean_x) * (y - mean_y);
+            yy += (y - mean_y) * (y - mean_y);
+        }
+        if xx <= 0.0 {
+            return None;
+        }
+        let slope = xy / xx;
+        // A flat line through flat points explains all there is
+        let r_squared = if yy > 0.0 { xy * xy / (xx * yy) } else { 1.0 };
+        Some(Fit {
+            slope,
+            intercept: mean_y - slope * mean_x,
+            r_squared,
+        })
+    }
+}
+
+impl BoxCounting {
+    /// Counts the boxes of every size in `sizes` that `points` fall into, `None` without
+    /// points or two different sizes.
+    pub fn new(points: &[DVec2], sizes: &[f64]) -> Option<Self> {
+        let min = points.iter().copied().reduce(DVec2::min)?;
+        let max = points.iter().copied().reduce(DVec2::max)?;
+        let counts: Vec<(f64, usize)> = sizes
+            .iter()
+            .map(|&size| {
+                // Points on the far edge belong to the last box rather than one of their own
+                let last = ((max - min) / size).ceil() - 1.0;
+                let boxes: HashSet<(i64, i64)> = points
+                    .iter()
+                    .map(|point| {
+                        let cell = ((*point - min) / size).floor().min(last.max(DVec2::ZERO));
+                        (cell.x as i64, cell.y as i64)
+                    })
+                    .collect();
+                (size, boxes.len())
+            })
+            .collect();
+        let logs: Vec<(f64, f64)> = counts
+            .iter()
+            .map(|&(size, boxes)| ((1.0 / size).ln(), (boxes as f64).ln()))
+            .collect();
+        Some(BoxCounting {
+            counts,
+            fit: Fit::of(&logs)?,
+        })
+    }
+
+    /// Counts over shapes as they are drawn, outlines only.
+    ///
+    /// Boxes halve in size from a quarter of the shapes' extent down to twice their
+    /// typical detail (the median segment, side, radius or point size), below which
+    /// every curve looks smooth. `None` when that leaves fewer than three sizes.
+    pub fn of(shapes: &[Shape]) -> Option<Self> {
+        let mut details: Vec<f64> = shapes.iter().flat_map(details).collect();
+        details.sort_by(f64::total_cmp);
+        let finest = 2.0 * *details.get(details.len() / 2)?;
+
+        let points = sample(shapes, finest / 4.0);
+        let min = points.iter().copied().reduce(DVec2::min)?;
+        let max = points.iter().copied().reduce(DVec2::max)?;
+        let extent = (max - min).max_element();
+        let sizes: Vec<f64> = (2..)
+            .map(|halvings| extent / 2f64.powi(halvings))
+            .take_while(|size| *size >= finest)
+            .collect();
+        if sizes.len() < 3 {
+            return None;
+        }
+        BoxCounting::new(&points, &sizes)
+    }
+
+    pub fn dimension(&self) -> f64 {
+        self.fit.slope
+    }
+}
+
+// The table of counts, then the fitted line
+impl fmt::Display for BoxCounting {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{:>12}  {:>10}", "box size", "boxes")?;
+        for (size, boxes) in &self.counts {
+            writeln!(f, "{size:>12.3}  {boxes:>10}")?;
+        }
+        write!(
+            f,
+            "log boxes = {:.4} log (1 / size) {} {:.3}, r² = {:.5}",
+            self.fit.slope,
+            if self.fit.intercept < 0.0 { '-' } else { '+' },
+            self.fit.intercept.abs(),
+            self.fit.r_squared
+        )
+    }
+}
+
+// Lengths of the details a shape is drawn with
+fn details(shape: &Shape) -> Vec<f64> {
+    let sides = |points: &[DVec2], closed: bool| -> Vec<f64> {
+        let last = points.len().saturating_sub(usize::from(!closed));
+        (0..last)
+            .map(|i| points[i].distance(points[(i + 1) % points.len()]))
+            .filter(|length| *length > 0.0)
+            .collect()
+    };
+    match shape {
+        Shape::Polyline(points) => sides(&as_f64(points), false),
+        Shape::Line(start, end) => sides(&[start.as_f64(), end.as_f64()], false),
+        Shape::Tri(corners) => sides(&as_f64(corners), true),
+        Shape::Polygon(points) => sides(&as_f64(points), true),
+        Shape::Circle { radius, .. } => vec![f64::from(*radius)],
+        Shape::Points { size, .. } => vec![f64::from(*size)],
+    }
+}
+
+// Points along the outline of every shape, at most `spacing` apart
+fn sample(shapes: &[Shape], spacing: f64) -> Vec<DVec2> {
+    let mut points = Vec::new();
+    let mut along = |from: DVec2, to: DVec2| {
+        // Nothing but the end for a point on its own
+        let steps = (from.distance(to) / spacing).ceil() as usize;
+        points.extend((0..steps).map(|step| from.lerp(to, step as f64 / steps as f64)));
+        points.push(to);
+    };
+    for shape in shapes {
+        match shape {
+            Shape::Polyline(line) => {
+                for pair in as_f64(line).windows(2) {
+                    along(pair[0], pair[1]);
+                }
+            }
+            Shape::Line(start, end) => along(start.as_f64(), end.as_f64()),
+            Shape::Tri(corners) => outline(&as_f64(corners), &mut along),
+            Shape::Polygon(corners) => outline(&as_f64(corners), &mut along),
+            Shape::Circle { center, radius } => {
+                let radius = f64::from(*radius);
+                let steps = (TAU * radius / spacing).ceil().max(8.0) as usize;
+                let corners: Vec<DVec2> = (0..steps)
+                    .map(|step| {
+                        let angle = TAU * step as f64 / steps as f64;
+                        center.as_f64() + DVec2::new(angle.cos(), angle.sin()) * radius
+                    })
+                    .collect();
+                outline(&corners, &mut along);
+            }
+            Shape::Points { points: dots, .. } => {
+                for dot in dots {
+                    along(dot.as_f64(), dot.as_f64());
+                }
+            }
+        }
+    }
+    points
+}
+
+fn outline(corners: &[DVec2], along: &mut impl FnMut(DVec2, DVec2)) {
+    for (i, corner) in corners.iter().enumerate() {
+        along(*corner, corners[(i + 1) % corners.len()]);
+    }
+}
+
+fn as_f64(points: &[Point2]) -> Vec<DVec2> {
+    points.iter().map(|point| point.as_f64()).collect()
+}
+
+/// The dimension a fractal should have.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Theory {
+    Similar(Dimension), // Copies all scaled down by the same ratio
+    // Copies scaled down by these ratios, with `D` solving `sum of ratio^D = 1`
+    Ratios(Vec<f64>),
+    Known { value: f64, source: &'static str },
+}
+
+impl Theory {
+    /// `Similar` when every copy has the same ratio, `Ratios` otherwise.
+    pub fn copies(ratios: Vec<f64>) -> Self {
+        match ratios.first() {
+            Some(&first) if ratios.iter().all(|r| (r - first).abs() < 1e-9) => {
+                Theory::Similar(Dimension {
+                    copies: ratios.len() as f64,
+                    scale: 1.0 / first,
+                })
+            }
+            _ => Theory::Ratios(ratios),
+        }
+    }
+
+    pub fn value(&self) -> f64 {
+        match self {
+            Theory::Similar(dimension) => dimension.value(),
+            Theory::Ratios(ratios) => {
+                // The sum only shrinks as `D` grows, so halve the bracket around it
+                let sum = |d: f64| ratios.iter().map(|r| r.powf(d)).sum::<f64>();
+                let (mut low, mut high) = (0.0, 16.0);
+                for _ in 0..64 {
+                    let middle = (low + high) / 2.0;
+                    if sum(middle) > 1.0 {
+                        low = middle;
+                    } else {
+                        high = middle;
+                    }
+                }
+                (low + high) / 2.0
+            }
+            Theory::Known { value, .. } => *value,
+        }
+    }
+}
+
+// e.g. `log 4 / log 3 = 1.2619`
+impl fmt::Display for Theory {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Theory::Similar(dimension) => write!(f, "{dimension}"),
+            Theory::Ratios(ratios) => {
+                let ratios: Vec<String> = ratios.iter().map(|r| format!("{r:.3}")).collect();
+                write!(
+                    f,
+                    "sum of r^D = 1 for r = {}, D = {:.4}",
+                    ratios.join(", "),
+                    self.value()
+                )
+            }
+            Theory::Known { value, source } => write!(f, "{value:.4} ({source})"),
+        }
+    }
+}
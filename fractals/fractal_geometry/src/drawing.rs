@@ -0,0 +1,142 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::Point2;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rgb {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Rgb {
+    pub const fn new(r: u8, g: u8, b: u8) -> Self {
+        Rgb { r, g, b }
+    }
+}
+
+impl FromStr for Rgb {
+    type Err = String;
+
+    // `black`, `white` or a hex color like `#1e90ff`
+    fn from_str(color: &str) -> Result<Self, Self::Err> {
+        match color {
+            "black" => return Ok(BLACK),
+            "white" => return Ok(WHITE),
+            _ => {}
+        }
+        let invalid = || format!("invalid color `{color}`, expected black, white or #rrggbb");
+        let hex = color.strip_prefix('#').unwrap_or(color);
+        if hex.len() != 6 || !hex.is_ascii() {
+            return Err(invalid());
+        }
+        let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).map_err(|_| invalid());
+        Ok(Rgb::new(channel(0)?, channel(2)?, channel(4)?))
+    }
+}
+
+// Hex, so it reads back with `parse`
+impl fmt::Display for Rgb {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    }
+}
+
+impl Rgb {
+    /// `t` of the way from `self` to `other`, with `t` from 0 to 1.
+    pub fn mix(self, other: Rgb, t: f32) -> Rgb {
+        let t = t.clamp(0.0, 1.0);
+        let channel =
+            |a: u8, b: u8| (f32::from(a) + (f32::from(b) - f32::from(a)) * t).round() as u8;
+        Rgb::new(
+            channel(self.r, other.r),
+            channel(self.g, other.g),
+            channel(self.b, other.b),
+        )
+    }
+}
+
+/// The color `t` of the way along `colors`, which are spread evenly from 0 to 1 and
+/// blended in between. Black without any colors.
+pub fn gradient(colors: &[Rgb], t: f32) -> Rgb {
+    let Some(last) = colors.len().checked_sub(1) else {
+        return BLACK;
+    };
+    let position = t.clamp(0.0, 1.0) * last as f32;
+    let index = (position.floor() as usize).min(last.saturating_sub(1));
+    match colors.get(index + 1) {
+        Some(next) => colors[index].mix(*next, position - index as f32),
+        None => colors[index],
+    }
+}
+
+pub const BLACK: Rgb = Rgb::new(0, 0, 0);
+pub const WHITE: Rgb = Rgb::new(255, 255, 255);
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Style {
+    pub fill: Option<Rgb>,
+    pub stroke: Option<Rgb>,
+    pub stroke_weight: f32,
+}
+
+impl Style {
+    pub fn fill(color: Rgb) -> Self {
+        Style {
+            fill: Some(color),
+            stroke: None,
+            stroke_weight: 0.0,
+        }
+    }
+
+    pub fn stroke(color: Rgb, stroke_weight: f32) -> Self {
+        Style {
+            fill: None,
+            stroke: Some(color),
+            stroke_weight,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Shape {
+    Polyline(Vec<Point2>),
+    Line(Point2, Point2),
+    Tri([Point2; 3]),
+    Polygon(Vec<Point2>), // Closed, unlike a polyline
+    Circle { center: Point2, radius: f32 },
+    // Squares `size` across, e.g. a point cloud
+    Points { points: Vec<Point2>, size: f32 },
+}
+
+/// Everything a sketch puts on screen for one frame, in nannou's coordinate system
+/// (origin in the middle, y pointing up) over a `width` x `height` view.
+///
+/// Sketches build one of these from the geometry and hand it to a backend: the nannou
+/// adapter for the window, or one of the headless exporters.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Drawing {
+    pub width: f32,
+    pub height: f32,
+    pub background: Rgb,
+    pub shapes: Vec<(Shape, Style)>,
+    // Text for the window to show over the shapes, e.g. measurements; files leave it out
+    pub overlay: Option<String>,
+}
+
+impl Drawing {
+    pub fn new(width: f32, height: f32, background: Rgb) -> Self {
+        Drawing {
+            width,
+            height,
+            background,
+            shapes: Vec::new(),
+            overlay: None,
+        }
+    }
+
+    pub fn push(&mut self, shape: Shape, style: Style) {
+        self.shapes.push((shape, style));
+    }
+}
@@ -0,0 +1,63 @@
+use crate::{rotate, Point2};
+
+/// Smallest axis-aligned box around all points, as `(min, max)`.
+pub fn bounds(polylines: &[Vec<Point2>]) -> Option<(Point2, Point2)> {
+    let mut points = polylines.iter().flatten();
+    let first = *points.next()?;
+    Some(points.fold((first, first), |(min, max), &p| (min.min(p), max.max(p))))
+}
+
+/// Moves, scales and rotates polylines so the first point lands on `start` and the last
+/// point on `end`. Meant for open curves, e.g. to lay an L-system over a `koch_line`.
+pub fn fit_between(polylines: &[Vec<Point2>], start: Point2, end: Point2) -> Vec<Vec<Point2>> {
+    let (Some(first), Some(last)) = (
+        polylines.first().and_then(|line| line.first()),
+        polylines.last().and_then(|line| line.last()),
+    ) else {
+        return Vec::new();
+    };
+    let (from, to) = (*last - *first, end - start);
+    if from.length() == 0.0 {
+        return polylines.to_vec(); // closed curve, there is no direction to match
+    }
+
+    let scale = to.length() / from.length();
+    let angle = to.y.atan2(to.x) - from.y.atan2(from.x);
+    polylines
+        .iter()
+        .map(|line| {
+            line.iter()
+                .map(|&p| start + rotate((p - *first) * scale, angle))
+                .collect()
+        })
+        .collect()
+}
+
+/// Scales polylines uniformly so they fill a `width` x `height` box centered on `center`.
+pub fn fit_into(
+    polylines: &[Vec<Point2>],
+    center: Point2,
+    width: f32,
+    height: f32,
+) -> Vec<Vec<Point2>> {
+    let Some((min, max)) = bounds(polylines) else {
+        return Vec::new();
+    };
+    let size = max - min;
+    // A straight line has no extent along one axis, so only the other one limits it
+    let scale = match (size.x > 0.0, size.y > 0.0) {
+        (true, true) => (width / size.x).min(height / size.y),
+        (true, false) => width / size.x,
+        (false, true) => height / size.y,
+        (false, false) => 1.0,
+    };
+    let middle = (min + max) / 2.0;
+    polylines
+        .iter()
+        .map(|line| {
+            line.iter()
+                .map(|&p| center + (p - middle) * scale)
+                .collect()
+        })
+        .collect()
+}
@@ -0,0 +1,646 @@
+use std::f32::consts::TAU;
+use std::f64::consts::PI;
+use std::fmt;
+use std::str::FromStr;
+
+use glam::DVec2;
+
+use crate::camera::Camera;
+use crate::{pt2, Point2};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KochType {
+    Linear,
+    Radial(u32), // Number of sides
+}
+
+/// Which side of each segment the bump goes on.
+///
+/// `Outward` is the side `koch_line` has always used: to the left of the direction of
+/// travel, i.e. above a left-to-right line. `Radial` walks its sides counter-clockwise, so
+/// there outward bumps point into the circle.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Bump {
+    Outward,
+    Inward,
+    Alternating, // Outward at the top level, then flipping at every level below
+}
+
+impl Bump {
+    // +1 for a bump on the outward side, -1 for inward; `level` counts down from the top
+    fn sign(self, level: u32) -> f32 {
+        match self {
+            Bump::Outward => 1.0,
+            Bump::Inward => -1.0,
+            Bump::Alternating if level.is_multiple_of(2) => 1.0,
+            Bump::Alternating => -1.0,
+        }
+    }
+}
+
+impl FromStr for Bump {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name {
+            "outward" => Ok(Bump::Outward),
+            "inward" => Ok(Bump::Inward),
+            "alternating" => Ok(Bump::Alternating),
+            _ => Err(format!(
+                "unknown bump `{name}`, options include: outward, inward or alternating"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Bump {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Bump::Outward => "outward",
+            Bump::Inward => "inward",
+            Bump::Alternating => "alternating",
+        })
+    }
+}
+
+/// Shape of the generator that replaces every segment.
+///
+/// Each segment is split into three parts by `ratios` (normalized, so `[1.0, 1.0, 1.0]`
+/// and `[0.3, 0.4, 0.3]` both work), and the middle part is replaced by two sides of an
+/// isosceles triangle whose base angles are `angle` degrees.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct KochParams {
+    pub angle: f32,
+    pub ratios: [f32; 3],
+    pub bump: Bump,
+}
+
+impl Default for KochParams {
+    // The classic Koch curve
+    fn default() -> Self {
+        KochParams {
+            angle: 60.0,
+            ratios: [1.0, 1.0, 1.0],
+            bump: Bump::Outward,
+        }
+    }
+}
+
+impl KochParams {
+    /// Cesàro fractal: a sharp `angle` with the split chosen so all four new segments have
+    /// the same length, bumped inward.
+    pub fn cesaro(angle: f32) -> Self {
+        let side = 1.0;
+        let middle = 2.0 * angle.to_radians().cos();
+        KochParams {
+            angle,
+            ratios: [side, middle, side],
+            bump: Bump::Inward,
+        }
+    }
+
+    /// The classic generator with every bump flipped inward.
+    pub fn anti_snowflake() -> Self {
+        KochParams {
+            bump: Bump::Inward,
+            ..KochParams::default()
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.angle.is_nan() || self.angle <= 0.0 || self.angle >= 90.0 {
+            return Err(format!(
+                "bump angle must be between 0 and 90 degrees, got {}",
+                self.angle
+            ));
+        }
+        let negative = |ratio: &f32| ratio.is_nan() || *ratio < 0.0;
+        if self.ratios.iter().any(negative) || self.ratios[1] <= 0.0 {
+            return Err(format!(
+                "split ratios must not be negative and the middle one must be positive, got {:?}",
+                self.ratios
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Where the curves of a radial Koch shape go.
+///
+/// The shape walks the edges of the star polygon {sides/skip} inscribed in a circle and
+/// replaces each edge with a Koch curve. `skip` 1 is a regular polygon, `RadialLayout::new(5)
+/// .star(2)` is a pentagram. When `sides` and `skip` share a divisor the star falls apart
+/// into several loops, e.g. {6/2} is two triangles.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RadialLayout {
+    pub center: Point2,
+    pub radius: f32,
+    pub rotation: f32, // Degrees counter-clockwise, 0 puts the first vertex right of the center
+    pub sides: u32,
+    pub skip: u32,
+    pub depths: Vec<u32>, // Depth of each side, repeated around the shape; empty uses one depth
+}
+
+impl RadialLayout {
+    /// The layout `KochType::Radial` used to be fixed to: a regular polygon around the
+    /// origin with a radius of 300.
+    pub fn new(sides: u32) -> Self {
+        RadialLayout {
+            center: pt2(0.0, 0.0),
+            radius: 300.0,
+            rotation: 0.0,
+            sides,
+            skip: 1,
+            depths: Vec::new(),
+        }
+    }
+
+    /// A regular polygon on the circle with `start` and `end` as its diameter, starting at
+    /// `start`.
+    pub fn between(start: Point2, end: Point2, sides: u32) -> Self {
+        let center = (start + end) / 2.0;
+        let offset = start - center;
+        RadialLayout {
+            center,
+            radius: offset.length(),
+            rotation: offset.y.atan2(offset.x).to_degrees(),
+            ..RadialLayout::new(sides)
+        }
+    }
+
+    pub fn center(mut self, center: Point2) -> Self {
+        self.center = center;
+        self
+    }
+
+    pub fn radius(mut self, radius: f32) -> Self {
+        self.radius = radius;
+        self
+    }
+
+    pub fn rotation(mut self, degrees: f32) -> Self {
+        self.rotation = degrees;
+        self
+    }
+
+    pub fn star(mut self, skip: u32) -> Self {
+        self.skip = skip;
+        self
+    }
+
+    pub fn depths(mut self, depths: Vec<u32>) -> Self {
+        self.depths = depths;
+        self
+    }
+
+    /// Moves the shape to `center` and picks the largest radius that keeps the finished
+    /// curve inside a `width` x `height` box around it, whichever way the bumps point.
+    ///
+    /// Only the first few levels are generated, the rest is bounded, so the fit is exact
+    /// up to that depth and a hair on the safe side below it.
+    pub fn fit_into(
+        self,
+        center: Point2,
+        width: f32,
+        height: f32,
+        depth: u32,
+        params: &KochParams,
+    ) -> Self {
+        // Measure the curve on a unit circle; it is round enough that fitting its
+        // farthest point into the box's inscribed circle works for any rotation
+        const MEASURED: u32 = 6;
+        let unit = RadialLayout {
+            depths: self.depths.iter().map(|d| (*d).min(MEASURED)).collect(),
+            ..self.clone().center(pt2(0.0, 0.0)).radius(1.0)
+        };
+        let measured = koch_radial(&unit, depth.min(MEASURED), params)
+            .iter()
+            .flatten()
+            .map(|point| point.length())
+            .fold(0.0, f32::max);
+
+        // Every deeper level strays at most `stray` of a segment from it, on segments at
+        // most `longest` of their parents, starting from sides as long as a chord
+        let deepest = (0..self.sides as usize)
+            .map(|side| self.depth(side, depth))
+            .max()
+            .unwrap_or(0);
+        let (stray, longest) = Generator::new(params).spread();
+        let chord = 2.0 * (PI * f64::from(self.skip.max(1)) / f64::from(self.sides.max(1))).sin();
+        let rest: f64 = (MEASURED..deepest)
+            .map(|level| stray * chord * longest.powi(level as i32))
+            .sum();
+        let reach = measured + rest as f32;
+        let radius = if reach > 0.0 {
+            width.min(height) / 2.0 / reach
+        } else {
+            0.0
+        };
+        self.center(center).radius(radius)
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.skip == 0 || self.sides <= 2 * self.skip {
+            return Err(format!(
+                "a star polygon {{{}/{}}} needs at least one skipped vertex and more than twice \
+                 as many sides as skipped vertices",
+                self.sides, self.skip
+            ));
+        }
+        if self.radius.is_nan() || self.radius < 0.0 {
+            return Err(format!("radius must not be negative, got {}", self.radius));
+        }
+        Ok(())
+    }
+
+    /// Corners of each loop of the star polygon, ending on the corner they start on, for
+    /// `koch_in_view`.
+    pub fn corners(&self) -> Vec<Vec<Point2>> {
+        let skip = self.skip.max(1);
+        let steps = self.sides / gcd(self.sides, skip);
+        (0..self.loops())
+            .map(|first| {
+                (0..=steps)
+                    .map(|step| self.vertex(first + step * skip))
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Number of separate closed polylines the star polygon falls apart into.
+    pub fn loops(&self) -> u32 {
+        match self.sides {
+            0 => 0,
+            sides => gcd(sides, self.skip.max(1)),
+        }
+    }
+
+    pub(crate) fn depth(&self, side: usize, depth: u32) -> u32 {
+        match self.depths.len() {
+            0 => depth,
+            len => self.depths[side % len],
+        }
+    }
+
+    fn vertex(&self, index: u32) -> Point2 {
+        let angle =
+            self.rotation.to_radians() + (index % self.sides) as f32 * TAU / self.sides as f32;
+        self.center + pt2(angle.cos(), angle.sin()) * self.radius
+    }
+}
+
+/// Number of points in a closed radial curve with `sides` sides, or in a linear curve
+/// with `sides` 1: 4^depth points per side plus the one the curve ends on.
+///
+/// Saturates at `usize::MAX` instead of overflowing, so it can be used to check whether a
+/// depth is too large before generating anything.
+pub fn point_count(depth: u32, sides: u32) -> usize {
+    4usize
+        .saturating_pow(depth)
+        .saturating_mul(sides as usize)
+        .saturating_add(1)
+}
+
+/// Builds a Koch curve as a single polyline.
+///
+/// `Linear` runs from `start` to `end`. `Radial(n)` closes `n` curves around the circle
+/// that has `start` and `end` as its diameter, see `RadialLayout::between`, and the
+/// returned polyline ends where it started.
+pub fn koch_line(start: Point2, end: Point2, depth: u32, koch_type: KochType) -> Vec<Point2> {
+    koch_line_with(start, end, depth, koch_type, &KochParams::default())
+}
+
+/// Same as `koch_line`, with a custom generator.
+pub fn koch_line_with(
+    start: Point2,
+    end: Point2,
+    depth: u32,
+    koch_type: KochType,
+    params: &KochParams,
+) -> Vec<Point2> {
+    let mut points = Vec::new();
+    koch_line_into(&mut points, start, end, depth, koch_type, params);
+    points
+}
+
+/// Same as `koch_line_with`, but overwrites `points` instead of allocating a new buffer.
+///
+/// Reusing one buffer from frame to frame means no allocation at all once it has grown
+/// to the largest depth drawn.
+pub fn koch_line_into(
+    points: &mut Vec<Point2>,
+    start: Point2,
+    end: Point2,
+    depth: u32,
+    koch_type: KochType,
+    params: &KochParams,
+) {
+    match koch_type {
+        KochType::Linear => {
+            points.clear();
+            points.resize(point_count(depth, 1), start);
+            *points.last_mut().unwrap() = end;
+            koch_fill(points, params);
+        }
+        // A regular polygon is always a single loop
+        KochType::Radial(sides) => {
+            let layout = RadialLayout::between(start, end, sides);
+            if sides == 0 {
+                points.clear();
+            } else {
+                koch_loop_into(points, &layout, 0, depth, params);
+            }
+        }
+    }
+}
+
+/// Builds a radial Koch shape as one closed polyline per loop of its star polygon.
+///
+/// `depth` is used for every side unless the layout sets its own `depths`.
+pub fn koch_radial(layout: &RadialLayout, depth: u32, params: &KochParams) -> Vec<Vec<Point2>> {
+    let mut polylines = Vec::new();
+    koch_radial_into(&mut polylines, layout, depth, params);
+    polylines
+}
+
+/// Same as `koch_radial`, but overwrites `polylines`, reusing their buffers.
+pub fn koch_radial_into(
+    polylines: &mut Vec<Vec<Point2>>,
+    layout: &RadialLayout,
+    depth: u32,
+    params: &KochParams,
+) {
+    polylines.resize_with(layout.loops() as usize, Vec::new);
+    for (first, points) in polylines.iter_mut().enumerate() {
+        koch_loop_into(points, layout, first as u32, depth, params);
+    }
+}
+
+/// A Koch curve along `corners` as `camera` shows it in a `width` x `height` window, in
+/// window coordinates.
+///
+/// Each pair of neighbouring corners is one side, like the two ends of a linear curve or
+/// a loop from `RadialLayout::corners`. Only the parts of the curve inside the window are
+/// generated: `depth` levels deep at a scale of 1, and deeper as the camera zooms in, so
+/// the detail on screen stays the same without generating the whole curve at the depth
+/// that would take. Detail under half a pixel is left out. The curve comes back as one
+/// polyline for each stretch of it that is in view.
+pub fn koch_in_view(
+    corners: &[Point2],
+    depth: u32,
+    params: &KochParams,
+    camera: &Camera,
+    width: f32,
+    height: f32,
+) -> Vec<Vec<Point2>> {
+    let view = KochView::new(params, depth, camera, width, height);
+    let mut runs = Runs::default();
+    for side in corners.windows(2) {
+        view.refine(&mut runs, side[0].as_f64(), side[1].as_f64(), 0);
+    }
+    runs.finish()
+}
+
+// Deepest level `koch_in_view` goes to however far the camera zooms in
+const MAX_VIEW_DEPTH: u32 = 64;
+// Segments shorter than this on screen are not split any further
+const MIN_VIEW_PIXELS: f64 = 0.5;
+
+struct KochView<'a> {
+    generator: Generator<f64>,
+    bump: Bump,
+    reach: f64, // The curve on a segment stays within this many segment lengths of its middle
+    depth: u32,
+    camera: &'a Camera,
+    width: f32,
+    height: f32,
+}
+
+impl<'a> KochView<'a> {
+    fn new(params: &KochParams, depth: u32, camera: &'a Camera, width: f32, height: f32) -> Self {
+        let generator = Generator::new(params);
+
+        // If every part of a segment's curve stays within `reach` of its own middle, the
+        // whole curve stays within `distance + reach * length` of the middle over all four
+        // parts, which is at most `reach` itself for the `reach` worked out here
+        let (start, end) = (DVec2::ZERO, DVec2::X);
+        let [a, b, c] = generator.points_precise(start, end, 1.0);
+        let parts = [(start, a), (a, b), (b, c), (c, end)];
+        let longest = parts
+            .iter()
+            .map(|(start, end)| start.distance(*end))
+            .fold(0.0, f64::max);
+        let farthest = parts
+            .iter()
+            .map(|(start, end)| ((*start + *end) / 2.0).distance(DVec2::new(0.5, 0.0)))
+            .fold(0.0, f64::max);
+
+        let (reach, deeper) = if longest < 1.0 {
+            let reach = (farthest / (1.0 - longest)).max(0.5);
+            // Levels it takes the slowest shrinking part to get as small on screen as it
+            // would be at a scale of 1
+            let levels = camera.scale.max(1.0).ln() / -longest.ln();
+            (reach, levels.ceil() as u32)
+        } else {
+            // A generator that grows its parts has no bound to cull by
+            (f64::INFINITY, 0)
+        };
+
+        KochView {
+            generator,
+            bump: params.bump,
+            reach,
+            depth: depth.saturating_add(deeper).min(MAX_VIEW_DEPTH.max(depth)),
+            camera,
+            width,
+            height,
+        }
+    }
+
+    fn refine(&self, runs: &mut Runs, start: DVec2, end: DVec2, level: u32) {
+        let length = start.distance(end);
+        let middle = (start + end) / 2.0;
+        if !self
+            .camera
+            .sees(middle, length * self.reach, self.width, self.height)
+        {
+            runs.split();
+            return;
+        }
+        if level >= self.depth || length * self.camera.scale < MIN_VIEW_PIXELS {
+            runs.push(self.camera.to_screen(start), self.camera.to_screen(end));
+            return;
+        }
+
+        let sign = f64::from(self.bump.sign(level));
+        let [a, b, c] = self.generator.points_precise(start, end, sign);
+        for (start, end) in [(start, a), (a, b), (b, c), (c, end)] {
+            self.refine(runs, start, end, level + 1);
+        }
+    }
+}
+
+// Polylines built one segment at a time, broken wherever segments are left out
+#[derive(Default)]
+struct Runs {
+    polylines: Vec<Vec<Point2>>,
+    current: Vec<Point2>,
+}
+
+impl Runs {
+    fn push(&mut self, start: Point2, end: Point2) {
+        if self.current.is_empty() {
+            self.current.push(start);
+        }
+        self.current.push(end);
+    }
+
+    fn split(&mut self) {
+        if !self.current.is_empty() {
+            self.polylines.push(std::mem::take(&mut self.current));
+        }
+    }
+
+    fn finish(mut self) -> Vec<Vec<Point2>> {
+        self.split();
+        self.polylines
+    }
+}
+
+fn gcd(a: u32, b: u32) -> u32 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+// Overwrites `points` with the loop of `layout` that starts on vertex `first`
+fn koch_loop_into(
+    points: &mut Vec<Point2>,
+    layout: &RadialLayout,
+    first: u32,
+    depth: u32,
+    params: &KochParams,
+) {
+    let skip = layout.skip.max(1);
+    let steps = layout.sides / gcd(layout.sides, skip);
+    // Sides are numbered around the whole shape, loop after loop, for per-side depths
+    let sides = (first * steps..(first + 1) * steps).map(|side| layout.depth(side as usize, depth));
+
+    let len = sides
+        .clone()
+        .map(|depth| point_count(depth, 1) - 1)
+        .sum::<usize>()
+        + 1;
+    points.clear();
+    points.resize(len, layout.vertex(first));
+
+    // Each side starts where the previous one ended, so neighbouring sides share a point
+    let mut offset = 0;
+    for (step, depth) in sides.enumerate() {
+        let side = &mut points[offset..offset + point_count(depth, 1)];
+        side[0] = layout.vertex(first + step as u32 * skip);
+        *side.last_mut().unwrap() = layout.vertex(first + (step as u32 + 1) * skip);
+        koch_fill(side, params);
+        offset += side.len() - 1;
+    }
+}
+
+// Fills in a curve whose first and last points are already in place, one level at a time:
+// every level replaces each segment of the level above, `stride` points apart, with the
+// four segments of the generator
+fn koch_fill(points: &mut [Point2], params: &KochParams) {
+    let generator = Generator::new(params).single();
+    let mut stride = points.len() - 1;
+    let mut level = 0;
+
+    while stride >= 4 {
+        let quarter = stride / 4;
+        let sign = params.bump.sign(level);
+        for start in (0..points.len() - 1).step_by(stride) {
+            let [bump_start, apex, bump_end] =
+                generator.points(points[start], points[start + stride], sign);
+            points[start + quarter] = bump_start;
+            points[start + 2 * quarter] = apex;
+            points[start + 3 * quarter] = bump_end;
+        }
+        stride = quarter;
+        level += 1;
+    }
+}
+
+// Everything about the generator that does not depend on the segment, worked out once
+// instead of for each of the millions of segments of a deep curve. Worked out in `f64`
+// for `koch_in_view`, whose deep zooms see every last digit.
+struct Generator<T> {
+    bump_start: T, // Where the bump starts and ends, as fractions of the segment
+    bump_end: T,
+    sin: T, // Turns the middle part into the first side of the bump
+    cos: T,
+}
+
+impl Generator<f64> {
+    fn new(params: &KochParams) -> Self {
+        let [first, middle, _] = params.ratios.map(f64::from);
+        let total: f64 = params.ratios.iter().copied().map(f64::from).sum();
+        let (sin, cos) = f64::from(params.angle).to_radians().sin_cos();
+        // Sides of the bump, long enough to meet above the middle of the removed part
+        let scale = 0.5 / cos;
+        Generator {
+            bump_start: first / total,
+            bump_end: (first + middle) / total,
+            sin: sin * scale,
+            cos: cos * scale,
+        }
+    }
+
+    // How far the generator strays from its segment and how long its longest piece is,
+    // both as fractions of the segment
+    fn spread(&self) -> (f64, f64) {
+        let middle = self.bump_end - self.bump_start;
+        let side = middle * self.sin.hypot(self.cos);
+        let longest = side.max(self.bump_start).max(1.0 - self.bump_end);
+        (middle * self.sin, longest)
+    }
+
+    fn single(&self) -> Generator<f32> {
+        Generator {
+            bump_start: self.bump_start as f32,
+            bump_end: self.bump_end as f32,
+            sin: self.sin as f32,
+            cos: self.cos as f32,
+        }
+    }
+
+    // Same as `points` in `f64`, for curves seen through a zoomed in camera
+    fn points_precise(&self, start: DVec2, end: DVec2, sign: f64) -> [DVec2; 3] {
+        let bump_start = start + (end - start) * self.bump_start;
+        let bump_end = start + (end - start) * self.bump_end;
+        let middle = bump_end - bump_start;
+        let (sin, cos) = (self.sin * sign, self.cos);
+        let side = DVec2::new(
+            middle.x * cos - middle.y * sin,
+            middle.x * sin + middle.y * cos,
+        );
+
+        [bump_start, bump_start + side, bump_end]
+    }
+}
+
+impl Generator<f32> {
+    // The three points the generator puts between `start` and `end`, with the bump on the
+    // outward side for `sign` 1 and the inward side for -1
+    fn points(&self, start: Point2, end: Point2, sign: f32) -> [Point2; 3] {
+        let bump_start = start + (end - start) * self.bump_start;
+        let bump_end = start + (end - start) * self.bump_end;
+        let middle = bump_end - bump_start;
+        let sin = self.sin * sign;
+        let side = pt2(
+            middle.x * self.cos - middle.y * sin,
+            middle.x * sin + middle.y * self.cos,
+        );
+
+        [bump_start, bump_start + side, bump_end]
+    }
+}
@@ -0,0 +1,29 @@
+//! Geometry for the fractal sketches.
+//!
+//! Everything in here returns plain points and polylines and never touches a nannou
+//! window, so curves can be generated (and checked) without a GPU. Rendering lives in
+//! `fractal_nannou` (the window) and `fractal_export` (files).
+
+pub mod apollonian;
+pub mod budget;
+pub mod camera;
+pub mod cantor;
+pub mod circles;
+pub mod dimension;
+pub mod drawing;
+pub mod fit;
+pub mod koch;
+pub mod lsystem;
+pub mod sierpinski;
+
+// nannou's `Point2` is glam's `Vec2`, so points produced here can be handed straight to
+// `nannou::Draw` without any conversion.
+pub use glam::{vec2 as pt2, Vec2};
+pub type Point2 = Vec2;
+
+// Same rotation nannou provides through `Vec2Rotate`, kept here so the geometry does not
+// need the nannou prelude.
+pub(crate) fn rotate(v: Vec2, radians: f32) -> Vec2 {
+    let (sin, cos) = radians.sin_cos();
+    pt2(v.x * cos - v.y * sin, v.x * sin + v.y * cos)
+}
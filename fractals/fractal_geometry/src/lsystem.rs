@@ -0,0 +1,409 @@
+use std::fmt;
+use std::str::FromStr;
+
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use crate::{pt2, Point2};
+
+/// An L-system drawn with turtle graphics.
+///
+/// Turtle commands:
+/// - `F`, `G`: move forward one step, drawing a line
+/// - `f`: move forward one step without drawing
+/// - `+`, `-`: turn left (counter-clockwise) or right by `angle`
+/// - `[`, `]`: save or restore the turtle's position and heading
+///
+/// Any other symbol (e.g. `X`, `A`, `B`) only takes part in the rewriting.
+///
+/// Rules may be stochastic (several weighted rules for one symbol) or context-sensitive
+/// (they only apply next to given neighbours). Random choices come from an RNG seeded
+/// with `seed`, so the same seed always grows the same system.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LSystem {
+    pub axiom: String,
+    pub rules: Vec<Rule>,
+    pub angle: f32,   // Turn angle in degrees
+    pub heading: f32, // Initial heading in degrees, 0 points along +x
+    pub step: f32,    // Length of a single `F`
+    pub seed: u64,
+    pub ignore: String, // Symbols skipped when looking for a rule's neighbours
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Rule {
+    pub symbol: char,
+    pub replacement: String,
+    pub left: Option<char>,  // Required neighbour before the symbol
+    pub right: Option<char>, // Required neighbour after the symbol
+    pub weight: f32,         // Relative odds among the rules that match
+}
+
+impl Rule {
+    pub fn new(symbol: char, replacement: &str) -> Self {
+        Rule {
+            symbol,
+            replacement: replacement.to_string(),
+            left: None,
+            right: None,
+            weight: 1.0,
+        }
+    }
+
+    pub fn left(mut self, neighbour: char) -> Self {
+        self.left = Some(neighbour);
+        self
+    }
+
+    pub fn right(mut self, neighbour: char) -> Self {
+        self.right = Some(neighbour);
+        self
+    }
+
+    pub fn weight(mut self, weight: f32) -> Self {
+        self.weight = weight;
+        self
+    }
+
+    // Number of neighbours the rule depends on, more specific rules win
+    fn context_len(&self) -> usize {
+        self.left.is_some() as usize + self.right.is_some() as usize
+    }
+
+    fn matches(&self, symbol: char, left: Option<char>, right: Option<char>) -> bool {
+        self.symbol == symbol
+            && (self.left.is_none() || self.left == left)
+            && (self.right.is_none() || self.right == right)
+    }
+}
+
+impl LSystem {
+    pub fn new(axiom: &str, angle: f32) -> Self {
+        LSystem {
+            axiom: axiom.to_string(),
+            rules: Vec::new(),
+            angle,
+            heading: 0.0,
+            step: 1.0,
+            seed: 0,
+            ignore: "+-".to_string(),
+        }
+    }
+
+    pub fn rule(self, symbol: char, replacement: &str) -> Self {
+        self.add_rule(Rule::new(symbol, replacement))
+    }
+
+    pub fn add_rule(mut self, rule: Rule) -> Self {
+        self.rules.push(rule);
+        self
+    }
+
+    pub fn heading(mut self, degrees: f32) -> Self {
+        self.heading = degrees;
+        self
+    }
+
+    pub fn step(mut self, step: f32) -> Self {
+        self.step = step;
+        self
+    }
+
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    pub fn ignore(mut self, symbols: &str) -> Self {
+        self.ignore = symbols.to_string();
+        self
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        match self
+            .rules
+            .iter()
+            .find(|rule| !rule.weight.is_finite() || rule.weight < 0.0)
+        {
+            Some(rule) => Err(format!(
+                "rule weights must be numbers no less than 0, got {} for `{}` -> `{}`",
+                rule.weight, rule.symbol, rule.replacement
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /// Applies the production rules `iterations` times to the axiom.
+    pub fn expand(&self, iterations: u32) -> String {
+        let mut rng = ChaCha8Rng::seed_from_u64(self.seed);
+        let mut current: Vec<char> = self.axiom.chars().collect();
+        for _ in 0..iterations {
+            let mut next = Vec::with_capacity(current.len() * 2);
+            for (i, &symbol) in current.iter().enumerate() {
+                match self.choose(&current, i, &mut rng) {
+                    Some(rule) => next.extend(rule.replacement.chars()),
+                    None => next.push(symbol),
+                }
+            }
+            current = next;
+        }
+        current.into_iter().collect()
+    }
+
+    // Picks the rule for `symbols[i]`: the most specific matching rules win, and ties are
+    // settled by weight
+    fn choose(&self, symbols: &[char], i: usize, rng: &mut ChaCha8Rng) -> Option<&Rule> {
+        let symbol = symbols[i];
+        if !self.rules.iter().any(|rule| rule.symbol == symbol) {
+            return None;
+        }
+
+        let (left, right) = (self.left_of(symbols, i), self.right_of(symbols, i));
+        let matching: Vec<&Rule> = self
+            .rules
+            .iter()
+            .filter(|rule| rule.matches(symbol, left, right))
+            .collect();
+        let specificity = matching.iter().map(|rule| rule.context_len()).max()?;
+        let candidates: Vec<&Rule> = matching
+            .into_iter()
+            .filter(|rule| rule.context_len() == specificity)
+            .collect();
+
+        if candidates.len() == 1 {
+            return Some(candidates[0]);
+        }
+        // Weights `validate` would reject count as 0, and without any odds left the first
+        // rule wins
+        let weight = |rule: &Rule| {
+            if rule.weight.is_finite() {
+                rule.weight.max(0.0)
+            } else {
+                0.0
+            }
+        };
+        let total: f32 = candidates.iter().map(|rule| weight(rule)).sum();
+        if !(total > 0.0 && total.is_finite()) {
+            return candidates.first().copied();
+        }
+        let mut pick = rng.gen_range(0.0..total);
+        for rule in &candidates {
+            if pick < weight(rule) {
+                return Some(rule);
+            }
+            pick -= weight(rule);
+        }
+        candidates.last().copied()
+    }
+
+    // Nearest symbol before `i` on the same branch or its parents, stepping over whole
+    // bracketed branches in between
+    fn left_of(&self, symbols: &[char], i: usize) -> Option<char> {
+        let mut depth = 0;
+        for &symbol in symbols[..i].iter().rev() {
+            match symbol {
+                ']' => depth += 1,
+                '[' if depth > 0 => depth -= 1,
+                '[' => {} // leaving our own branch, the parent's symbols still count
+                _ if depth > 0 || self.ignore.contains(symbol) => {}
+                _ => return Some(symbol),
+            }
+        }
+        None
+    }
+
+    // Nearest symbol after `i` on the same branch, stepping over side branches
+    fn right_of(&self, symbols: &[char], i: usize) -> Option<char> {
+        let mut depth = 0;
+        for &symbol in &symbols[i + 1..] {
+            match symbol {
+                '[' => depth += 1,
+                ']' if depth > 0 => depth -= 1,
+                ']' => return None, // end of our branch
+                _ if depth > 0 || self.ignore.contains(symbol) => {}
+                _ => return Some(symbol),
+            }
+        }
+        None
+    }
+
+    /// Expands the system and walks the turtle from `start`, one polyline per unbroken
+    /// stroke.
+    pub fn polylines(&self, iterations: u32, start: Point2) -> Vec<Vec<Point2>> {
+        turtle(
+            &self.expand(iterations),
+            start,
+            self.heading,
+            self.angle,
+            self.step,
+        )
+    }
+}
+
+/// Walks a turtle over `commands`, starting at `start` facing `heading` degrees.
+pub fn turtle(
+    commands: &str,
+    start: Point2,
+    heading: f32,
+    angle: f32,
+    step: f32,
+) -> Vec<Vec<Point2>> {
+    let mut polylines = Vec::new();
+    let mut current = vec![start];
+    let mut position = start;
+    // Headings stay in degrees so repeated 60° or 90° turns add up exactly
+    let mut heading = heading;
+    let mut stack = Vec::new();
+
+    // Starts a new stroke at `position` unless the current one is already there
+    let mut break_stroke = |current: &mut Vec<Point2>, position: Point2| {
+        if current.len() > 1 {
+            polylines.push(std::mem::replace(current, vec![position]));
+        } else {
+            *current = vec![position];
+        }
+    };
+
+    for command in commands.chars() {
+        match command {
+            'F' | 'G' => {
+                let radians = heading.to_radians();
+                position += pt2(radians.cos(), radians.sin()) * step;
+                current.push(position);
+            }
+            'f' => {
+                let radians = heading.to_radians();
+                position += pt2(radians.cos(), radians.sin()) * step;
+                break_stroke(&mut current, position);
+            }
+            '+' => heading += angle,
+            '-' => heading -= angle,
+            '[' => stack.push((position, heading)),
+            ']' => {
+                if let Some((saved_position, saved_heading)) = stack.pop() {
+                    position = saved_position;
+                    heading = saved_heading;
+                    break_stroke(&mut current, position);
+                }
+            }
+            _ => {}
+        }
+    }
+    if current.len() > 1 {
+        polylines.push(current);
+    }
+    polylines
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Preset {
+    Koch,
+    KochSnowflake,
+    QuadraticKoch,
+    Dragon,
+    Hilbert,
+    Gosper,
+    Plant,
+    Bush,
+    Coastline,
+    ContextPlant,
+}
+
+impl Preset {
+    pub const ALL: [Preset; 10] = [
+        Preset::Koch,
+        Preset::KochSnowflake,
+        Preset::QuadraticKoch,
+        Preset::Dragon,
+        Preset::Hilbert,
+        Preset::Gosper,
+        Preset::Plant,
+        Preset::Bush,
+        Preset::Coastline,
+        Preset::ContextPlant,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Preset::Koch => "koch",
+            Preset::KochSnowflake => "snowflake",
+            Preset::QuadraticKoch => "quadratic-koch",
+            Preset::Dragon => "dragon",
+            Preset::Hilbert => "hilbert",
+            Preset::Gosper => "gosper",
+            Preset::Plant => "plant",
+            Preset::Bush => "bush",
+            Preset::Coastline => "coastline",
+            Preset::ContextPlant => "context-plant",
+        }
+    }
+
+    pub fn lsystem(self) -> LSystem {
+        match self {
+            // Same curve as `koch_line` with `KochType::Linear`
+            Preset::Koch => LSystem::new("F", 60.0).rule('F', "F+F--F+F"),
+            // Walks the triangle clockwise so every bump points outwards
+            Preset::KochSnowflake => LSystem::new("F--F--F", 60.0).rule('F', "F+F--F+F"),
+            Preset::QuadraticKoch => LSystem::new("F", 90.0).rule('F', "F+F-F-F+F"),
+            Preset::Dragon => LSystem::new("F", 90.0).rule('F', "F+G").rule('G', "F-G"),
+            Preset::Hilbert => LSystem::new("A", 90.0)
+                .rule('A', "+BF-AFA-FB+")
+                .rule('B', "-AF+BFB+FA-"),
+            Preset::Gosper => LSystem::new("F", 60.0)
+                .rule('F', "F-G--G+F++FF+G-")
+                .rule('G', "+F-GG--G-F++F+G"),
+            Preset::Plant => LSystem::new("X", 25.0)
+                .rule('X', "F+[[X]-X]-F[-FX]+X")
+                .rule('F', "FF")
+                .heading(90.0),
+            // Every branch picks one of three shapes at random
+            Preset::Bush => LSystem::new("F", 25.7)
+                .add_rule(Rule::new('F', "F[+F]F[-F]F").weight(1.0))
+                .add_rule(Rule::new('F', "F[+F]F").weight(1.0))
+                .add_rule(Rule::new('F', "F[-F]F").weight(1.0))
+                .heading(90.0),
+            // Koch curve whose bumps flip inwards or outwards at random
+            Preset::Coastline => LSystem::new("F", 60.0)
+                .add_rule(Rule::new('F', "F+F--F+F").weight(1.0))
+                .add_rule(Rule::new('F', "F-F++F-F").weight(1.0)),
+            // Signals travel along the 0/1 markers and sprout branches where they meet
+            // (The Algorithmic Beauty of Plants, figure 1.31a)
+            Preset::ContextPlant => LSystem::new("F1F1F1", 22.5)
+                .ignore("+-F")
+                .add_rule(Rule::new('0', "0").left('0').right('0'))
+                .add_rule(Rule::new('0', "1[+F1F1]").left('0').right('1'))
+                .add_rule(Rule::new('1', "1").left('0').right('0'))
+                .add_rule(Rule::new('1', "1").left('0').right('1'))
+                .add_rule(Rule::new('0', "0").left('1').right('0'))
+                .add_rule(Rule::new('0', "1F1").left('1').right('1'))
+                .add_rule(Rule::new('1', "1").left('1').right('0'))
+                .add_rule(Rule::new('1', "0").left('1').right('1'))
+                .rule('+', "-")
+                .rule('-', "+")
+                .heading(90.0),
+        }
+    }
+}
+
+impl fmt::Display for Preset {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Preset {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Preset::ALL
+            .into_iter()
+            .find(|preset| preset.name() == name)
+            .ok_or_else(|| {
+                let names: Vec<_> = Preset::ALL.iter().map(|preset| preset.name()).collect();
+                format!(
+                    "unknown L-system `{name}`, options include: {}",
+                    names.join(", ")
+                )
+            })
+    }
+}
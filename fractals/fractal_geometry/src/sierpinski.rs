@@ -0,0 +1,392 @@
+use std::f64::consts::TAU;
+
+use glam::DVec2;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use crate::camera::Camera;
+use crate::Point2;
+
+/// Triangles of a Sierpinski triangle, each as `[start, end, top]`.
+pub fn sierpinski_triangles(
+    start: Point2,
+    top: Point2,
+    end: Point2,
+    depth: u32,
+) -> Vec<[Point2; 3]> {
+    let mut triangles = Vec::new();
+    sierpinski(&mut triangles, start, top, end, depth);
+    triangles
+}
+
+fn sierpinski(
+    triangles: &mut Vec<[Point2; 3]>,
+    start: Point2,
+    top: Point2,
+    end: Point2,
+    depth: u32,
+) {
+    if depth == 0 {
+        triangles.push([start, end, top]);
+    } else {
+        let mid_start_top = (start + top) / 2.0;
+        let mid_top_end = (top + end) / 2.0;
+        let mid_end_start = (end + start) / 2.0;
+
+        sierpinski(triangles, start, mid_start_top, mid_end_start, depth - 1);
+        sierpinski(triangles, mid_start_top, top, mid_top_end, depth - 1);
+        sierpinski(triangles, mid_end_start, mid_top_end, end, depth - 1);
+    }
+}
+
+/// The members of the Sierpinski family.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SierpinskiShape {
+    Triangle,
+    /// A square split into nine, keeping all but the middle one.
+    Carpet,
+    /// A regular polygon with `sides`, split into one copy at each corner, plus one in the
+    /// middle from five sides on (5 makes the pentaflake). The copies shrink just enough
+    /// to touch without overlapping.
+    Flake(u32),
+}
+
+impl SierpinskiShape {
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            SierpinskiShape::Flake(sides) if *sides < 3 => {
+                Err(format!("a flake needs at least 3 sides, got {sides}"))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// How many copies every polygon splits into.
+    pub fn copies(&self) -> u32 {
+        self.anchors().len() as u32
+    }
+
+    /// Sides of the polygons it is drawn with.
+    pub fn sides(&self) -> u32 {
+        self.outline().len() as u32
+    }
+
+    // The first polygon, counterclockwise and at most 2 across
+    fn outline(&self) -> Vec<DVec2> {
+        match self {
+            SierpinskiShape::Triangle => vec![
+                DVec2::new(-1.0, -0.75f64.sqrt()),
+                DVec2::new(1.0, -0.75f64.sqrt()),
+                DVec2::new(0.0, 0.75f64.sqrt()),
+            ],
+            SierpinskiShape::Carpet => vec![
+                DVec2::new(-1.0, -1.0),
+                DVec2::new(1.0, -1.0),
+                DVec2::new(1.0, 1.0),
+                DVec2::new(-1.0, 1.0),
+            ],
+            // Standing on a corner, so the pentaflake points up
+            SierpinskiShape::Flake(sides) => (0..*sides)
+                .map(|i| {
+                    let angle = TAU / 4.0 + TAU * f64::from(i) / f64::from(*sides);
+                    DVec2::new(angle.cos(), angle.sin())
+                })
+                .collect(),
+        }
+    }
+
+    // Every copy is the whole polygon shrunk by `ratio` towards one of these points
+    fn anchors(&self) -> Vec<DVec2> {
+        let outline = self.outline();
+        match self {
+            SierpinskiShape::Triangle => outline,
+            SierpinskiShape::Carpet => {
+                let middles = (0..4).map(|i| (outline[i] + outline[(i + 1) % 4]) / 2.0);
+                outline.iter().copied().chain(middles).collect()
+            }
+            SierpinskiShape::Flake(sides) if *sides >= 5 => {
+                // The middle copy shrinks towards the middle and stays put
+                outline.into_iter().chain([DVec2::ZERO]).collect()
+            }
+            SierpinskiShape::Flake(_) => outline,
+        }
+    }
+
+    /// How much smaller every copy is than the polygon it splits.
+    pub fn ratio(&self) -> f64 {
+        match self {
+            SierpinskiShape::Triangle => 0.5,
+            SierpinskiShape::Carpet => 1.0 / 3.0,
+            // Corner copies touch where the polygon's sides, projected onto the line
+            // between two neighbouring corners, add up to the length of that line
+            SierpinskiShape::Flake(sides) => {
+                let n = f64::from(*sides);
+                let reach: f64 = (1..=sides / 4)
+                    .map(|k| (TAU * f64::from(k) / n).cos())
+                    .sum();
+                1.0 / (2.0 * (1.0 + reach))
+            }
+        }
+    }
+}
+
+/// A member of the Sierpinski family fitted into a box: a polygon replaced by smaller
+/// copies of itself, and each of those by copies of its own, `depth` times over.
+///
+/// Every way of drawing it (`polygons`, `in_view` and `chaos_game`) works from the same
+/// shape and box, so they all line up.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Sierpinski {
+    pub shape: SierpinskiShape,
+    pub center: Point2,
+    pub width: f32,
+    pub height: f32,
+    pub equilateral: bool, // Keep the shape's proportions rather than stretch it over the box
+}
+
+impl Sierpinski {
+    /// The shape stretched over a 2x2 box around the origin.
+    pub fn new(shape: SierpinskiShape) -> Self {
+        Sierpinski {
+            shape,
+            center: Point2::ZERO,
+            width: 2.0,
+            height: 2.0,
+            equilateral: false,
+        }
+    }
+
+    pub fn fit_into(mut self, center: Point2, width: f32, height: f32) -> Self {
+        self.center = center;
+        self.width = width;
+        self.height = height;
+        self
+    }
+
+    pub fn equilateral(mut self, equilateral: bool) -> Self {
+        self.equilateral = equilateral;
+        self
+    }
+
+    /// The polygons left after `depth` rounds of splitting.
+    pub fn polygons(&self, depth: u32) -> Vec<Vec<Point2>> {
+        let mut pieces = Vec::new();
+        self.frame()
+            .split(&mut pieces, false, DVec2::ZERO, 1.0, 0, depth);
+        pieces.into_iter().map(|piece| piece.points).collect()
+    }
+
+    /// Every polygon on the way to `polygons`, from the first one down, each one right
+    /// before the copies it splits into. Drawing them in order paints over the parents'
+    /// holes, see `Piece`.
+    pub fn pieces(&self, depth: u32) -> Vec<Piece> {
+        let mut pieces = Vec::new();
+        self.frame()
+            .split(&mut pieces, true, DVec2::ZERO, 1.0, 0, depth);
+        pieces
+    }
+
+    /// The polygons as `camera` shows them in a `width` x `height` window, in window
+    /// coordinates and in the same order as `polygons`.
+    ///
+    /// Only polygons inside the window are split: `depth` times at a scale of 1, and more
+    /// the further the camera zooms in, so there is as much detail on screen at any zoom.
+    /// Polygons under a pixel across are not split any further.
+    pub fn in_view(
+        &self,
+        depth: u32,
+        camera: &Camera,
+        width: f32,
+        height: f32,
+    ) -> Vec<Vec<Point2>> {
+        let mut pieces = Vec::new();
+        self.view(depth, camera, width, height, false)
+            .refine(&mut pieces, DVec2::ZERO, 1.0, 0);
+        pieces.into_iter().map(|piece| piece.points).collect()
+    }
+
+    /// `pieces` for the polygons `in_view` splits, in window coordinates.
+    ///
+    /// Only the polygons that were split to get to what is on screen are included, and
+    /// the pieces that are not split any further can be at different levels.
+    pub fn pieces_in_view(
+        &self,
+        depth: u32,
+        camera: &Camera,
+        width: f32,
+        height: f32,
+    ) -> Vec<Piece> {
+        let mut pieces = Vec::new();
+        self.view(depth, camera, width, height, true)
+            .refine(&mut pieces, DVec2::ZERO, 1.0, 0);
+        pieces
+    }
+
+    fn view<'a>(
+        &self,
+        depth: u32,
+        camera: &'a Camera,
+        width: f32,
+        height: f32,
+        parents: bool,
+    ) -> SierpinskiView<'a> {
+        // Enough levels to shrink the polygons back to the size they have unzoomed
+        let deeper = (camera.scale.max(1.0).ln() / -self.shape.ratio().ln()).ceil() as u32;
+        SierpinskiView {
+            frame: self.frame(),
+            depth: depth.saturating_add(deeper).min(MAX_VIEW_DEPTH.max(depth)),
+            parents,
+            camera,
+            width,
+            height,
+        }
+    }
+
+    /// `points` points of the fractal found by the chaos game: starting on a corner, jump
+    /// again and again by `ratio` of the way towards a randomly picked anchor.
+    ///
+    /// Every jump lands on the fractal, so this shows the same shape as `polygons` at an
+    /// unlimited depth, as a cloud of points. The same `seed` always gives the same points.
+    pub fn chaos_game(&self, points: u32, seed: u64) -> Vec<Point2> {
+        let frame = self.frame();
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        let mut point = frame.anchors[0];
+        (0..points)
+            .map(|_| {
+                let anchor = frame.anchors[rng.gen_range(0..frame.anchors.len())];
+                point = anchor + (point - anchor) * frame.ratio;
+                point.as_f32()
+            })
+            .collect()
+    }
+
+    // The outline and anchors moved into the box
+    fn frame(&self) -> Frame {
+        let outline = self.shape.outline();
+        let (min, max) = outline.iter().fold(
+            (DVec2::splat(f64::INFINITY), DVec2::splat(f64::NEG_INFINITY)),
+            |(min, max), point| (min.min(*point), max.max(*point)),
+        );
+        let mut scale = DVec2::new(f64::from(self.width), f64::from(self.height)) / (max - min);
+        if self.equilateral {
+            scale = DVec2::splat(scale.min_element());
+        }
+        let middle = (min + max) / 2.0;
+        let place = |point: DVec2| self.center.as_f64() + (point - middle) * scale;
+        Frame {
+            outline: outline.into_iter().map(place).collect(),
+            anchors: self.shape.anchors().into_iter().map(place).collect(),
+            ratio: self.shape.ratio(),
+        }
+    }
+}
+
+/// A polygon of a Sierpinski fractal with its place in the recursion.
+///
+/// A piece that is not a leaf is covered by the copies it splits into, except for the
+/// holes between them. So filling the parents in one color and the leaves in another
+/// shows every hole, in the color of the parent it was cut out of.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Piece {
+    pub level: u32, // Splits it took to get here, 0 for the first polygon
+    pub leaf: bool, // Not split any further
+    pub points: Vec<Point2>,
+}
+
+// Deepest level `Sierpinski::in_view` goes to however far the camera zooms in
+const MAX_VIEW_DEPTH: u32 = 48;
+
+// Every polygon is the outline scaled by `scale` about the origin, then moved by `offset`
+struct Frame {
+    outline: Vec<DVec2>,
+    anchors: Vec<DVec2>,
+    ratio: f64,
+}
+
+impl Frame {
+    // Leaves `depth` levels down, and their parents too if `parents` is set
+    fn split(
+        &self,
+        pieces: &mut Vec<Piece>,
+        parents: bool,
+        offset: DVec2,
+        scale: f64,
+        level: u32,
+        depth: u32,
+    ) {
+        let leaf = level == depth;
+        if leaf || parents {
+            pieces.push(Piece {
+                level,
+                leaf,
+                points: self.place(offset, scale, |point| point.as_f32()),
+            });
+        }
+        if leaf {
+            return;
+        }
+        for (offset, scale) in self.children(offset, scale) {
+            self.split(pieces, parents, offset, scale, level + 1, depth);
+        }
+    }
+
+    // Shrinking by `ratio` towards an anchor, after placing the polygon itself
+    fn children(&self, offset: DVec2, scale: f64) -> impl Iterator<Item = (DVec2, f64)> + '_ {
+        self.anchors.iter().map(move |anchor| {
+            (
+                offset + *anchor * scale * (1.0 - self.ratio),
+                scale * self.ratio,
+            )
+        })
+    }
+
+    fn place(&self, offset: DVec2, scale: f64, to: impl Fn(DVec2) -> Point2) -> Vec<Point2> {
+        self.outline
+            .iter()
+            .map(|point| to(offset + *point * scale))
+            .collect()
+    }
+}
+
+struct SierpinskiView<'a> {
+    frame: Frame,
+    depth: u32,
+    parents: bool,
+    camera: &'a Camera,
+    width: f32,
+    height: f32,
+}
+
+impl SierpinskiView<'_> {
+    fn refine(&self, pieces: &mut Vec<Piece>, offset: DVec2, scale: f64, level: u32) {
+        let corners: Vec<DVec2> = self
+            .frame
+            .outline
+            .iter()
+            .map(|point| offset + *point * scale)
+            .collect();
+        let middle = corners.iter().sum::<DVec2>() / corners.len() as f64;
+        let radius = corners
+            .iter()
+            .map(|corner| corner.distance(middle))
+            .fold(0.0, f64::max);
+        if !self.camera.sees(middle, radius, self.width, self.height) {
+            return;
+        }
+        let leaf = level >= self.depth || radius * self.camera.scale < 0.5;
+        if leaf || self.parents {
+            let screen = |point| self.camera.to_screen(point);
+            pieces.push(Piece {
+                level,
+                leaf,
+                points: self.frame.place(offset, scale, screen),
+            });
+        }
+        if leaf {
+            return;
+        }
+        for (offset, scale) in self.frame.children(offset, scale) {
+            self.refine(pieces, offset, scale, level + 1);
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use fractal_geometry::apollonian::{Apollonian, GasketCircle};
+use fractal_geometry::budget::Estimate;
+use fractal_geometry::pt2;
+
+fn gasket(curvatures: [f64; 3], depth: u32) -> Vec<GasketCircle> {
+    Apollonian::new(curvatures).circles(pt2(10.0, -20.0), 300.0, depth, 0.5)
+}
+
+// Every circle inside the first one and none overlapping another
+fn assert_packed(circles: &[GasketCircle]) {
+    let outer = circles[0];
+    assert_eq!((outer.center, outer.radius), (pt2(10.0, -20.0), 300.0));
+    for (i, a) in circles.iter().enumerate().skip(1) {
+        assert!(
+            a.center.distance(outer.center) + a.radius < outer.radius + 1e-2,
+            "{a:?}"
+        );
+        for b in &circles[i + 1..] {
+            assert!(
+                a.center.distance(b.center) > a.radius + b.radius - 1e-2,
+                "{a:?} {b:?}"
+            );
+        }
+    }
+}
+
+#[test]
+fn the_classic_gasket_has_whole_curvatures() {
+    let circles = gasket([-1.0, 2.0, 2.0], 0);
+    let curvatures: Vec<f64> = circles.iter().map(|c| c.curvature).collect();
+    assert_eq!(curvatures, [-1.0, 2.0, 2.0, 3.0, 3.0]);
+    assert_eq!(circles[1].radius, 150.0);
+
+    let circles = gasket([-1.0, 2.0, 2.0], 4);
+    assert_eq!(circles.len() as u64, Estimate::apollonian(4).segments);
+    for circle in &circles {
+        let k = circle.curvature;
+        assert!((k - k.round()).abs() < 1e-6, "{circle:?}");
+    }
+    assert!(circles.iter().any(|c| c.curvature == 15.0));
+    assert_packed(&circles);
+}
+
+#[test]
+fn three_equal_circles_get_an_enclosing_one() {
+    let circles = gasket([1.0, 1.0, 1.0], 3);
+    assert_eq!(circles[0].curvature, -1.0);
+    // Three circles around the middle, 2 sqrt(3) - 3 of the enclosing size
+    let inner = circles[1].radius / circles[0].radius;
+    assert!((inner - (2.0 * 3f32.sqrt() - 3.0)).abs() < 1e-4, "{inner}");
+    assert_packed(&circles);
+
+    let circles = gasket([-6.0, 11.0, 14.0], 5);
+    assert_packed(&circles);
+}
+
+#[test]
+fn small_circles_are_left_out() {
+    let circles = Apollonian::default().circles(pt2(0.0, 0.0), 300.0, 20, 4.0);
+    assert!(circles[1..].iter().all(|c| c.radius >= 4.0));
+    assert!(circles.iter().any(|c| c.radius < 6.0));
+    assert!(circles.iter().any(|c| c.generation > 5));
+}
+
+#[test]
+fn triples_that_cannot_touch_are_errors() {
+    assert!(Apollonian::default().validate().is_ok());
+    assert!(Apollonian::new([0.0, 1.0, 1.0]).validate().is_err());
+    assert!(Apollonian::new([-1.0, -2.0, 3.0]).validate().is_err());
+    assert!(Apollonian::new([-1.0, 1.2, 1.2]).validate().is_err());
+    assert!(Apollonian::new([1.0, 1.0, 10.0]).validate().is_err());
+}
@@ -0,0 +1,54 @@
+use fractal_geometry::budget::{Budget, Estimate};
+use fractal_geometry::koch::{koch_line, koch_radial, KochParams, KochType, RadialLayout};
+use fractal_geometry::lsystem::Preset;
+use fractal_geometry::pt2;
+
+#[test]
+fn koch_estimates_match_the_generated_curves() {
+    let points = koch_line(pt2(0.0, 0.0), pt2(1.0, 0.0), 5, KochType::Linear);
+    let estimate = Estimate::koch_linear(5);
+    assert_eq!(estimate.vertices as usize, points.len());
+    assert_eq!(estimate.segments as usize, points.len() - 1);
+
+    let layout = RadialLayout::new(6).star(2).depths(vec![1, 3]);
+    let polylines = koch_radial(&layout, 0, &KochParams::default());
+    let vertices: usize = polylines.iter().map(Vec::len).sum();
+    assert_eq!(
+        Estimate::koch_radial(&layout, 0).vertices as usize,
+        vertices
+    );
+}
+
+#[test]
+fn lsystem_estimates_are_exact_for_deterministic_rules() {
+    for preset in [Preset::Koch, Preset::Dragon, Preset::Hilbert] {
+        let lsystem = preset.lsystem();
+        let commands = lsystem.expand(4);
+        let segments = commands.chars().filter(|c| matches!(c, 'F' | 'G')).count();
+        assert_eq!(
+            Estimate::lsystem(&lsystem, 4).segments as usize,
+            segments,
+            "{preset}"
+        );
+    }
+
+    // Random rules are bounded by their longest replacement
+    let bush = Preset::Bush.lsystem();
+    let segments = bush.expand(3).chars().filter(|&c| c == 'F').count();
+    assert!(Estimate::lsystem(&bush, 3).segments as usize >= segments);
+}
+
+#[test]
+fn huge_requests_are_refused_or_capped() {
+    let layout = RadialLayout::new(50);
+    let huge = Estimate::koch_radial(&layout, 15);
+    let budget = Budget::default();
+
+    assert!(budget.check(&huge).is_err());
+    assert_eq!(Estimate::koch_radial(&layout, 40).segments, u64::MAX);
+
+    let capped = budget.cap(15, |depth| Estimate::koch_radial(&layout, depth));
+    assert_eq!(capped, Some(8)); // 50 * 4^8 is 3.3 million, 4^9 would be 13 million
+    assert_eq!(Budget::new(10).cap(15, Estimate::koch_linear), Some(1));
+    assert_eq!(Budget::new(0).cap(15, Estimate::koch_linear), None);
+}
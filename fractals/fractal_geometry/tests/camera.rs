@@ -0,0 +1,85 @@
+use fractal_geometry::camera::Camera;
+use fractal_geometry::koch::{koch_in_view, koch_radial, KochParams, RadialLayout};
+use fractal_geometry::sierpinski::{Sierpinski, SierpinskiShape};
+use fractal_geometry::{pt2, Point2};
+use glam::DVec2;
+
+fn close(a: Point2, b: Point2) -> bool {
+    a.distance(b) < 1e-3
+}
+
+#[test]
+fn zooming_keeps_the_point_under_the_cursor() {
+    let camera = Camera {
+        center: DVec2::new(10.0, -5.0),
+        scale: 2.0,
+        rotation: 30.0,
+    };
+    let cursor = pt2(120.0, 80.0);
+    let world = camera.to_world(cursor);
+    assert!(close(camera.to_screen(world), cursor));
+
+    let zoomed = camera.zoomed_about(8.0, cursor);
+    assert_eq!(zoomed.scale, 16.0);
+    assert!(close(zoomed.to_screen(world), cursor));
+
+    let panned = camera.panned(pt2(15.0, -4.0));
+    assert!(close(panned.to_screen(world), cursor + pt2(15.0, -4.0)));
+}
+
+#[test]
+fn an_unmoved_camera_sees_the_whole_curve() {
+    let layout = RadialLayout::new(6).star(2).radius(200.0);
+    let params = KochParams::default();
+    let whole = koch_radial(&layout, 3, &params);
+    let seen: Vec<_> = layout
+        .corners()
+        .iter()
+        .flat_map(|corners| koch_in_view(corners, 3, &params, &Camera::default(), 800.0, 600.0))
+        .collect();
+
+    assert_eq!(seen.len(), whole.len());
+    for (seen, whole) in seen.iter().zip(&whole) {
+        assert_eq!(seen.len(), whole.len());
+        assert!(seen.iter().zip(whole).all(|(a, b)| close(*a, *b)));
+    }
+
+    let carpet = Sierpinski::new(SierpinskiShape::Carpet).fit_into(pt2(0.0, 0.0), 600.0, 500.0);
+    let seen = carpet.in_view(3, &Camera::default(), 800.0, 600.0);
+    assert_eq!(seen, carpet.polygons(3));
+}
+
+#[test]
+fn deep_zooms_only_generate_what_is_in_view() {
+    let layout = RadialLayout::new(3).radius(300.0);
+    let corners = &layout.corners()[0];
+    // Somewhere on the curve, a third of the way along its first side
+    let (first, second) = (corners[0].as_f64(), corners[1].as_f64());
+    let target = first + (second - first) / 3.0;
+    let (width, height) = (800.0, 600.0);
+
+    for scale in [1e3, 1e6, 1e9] {
+        let camera = Camera {
+            center: target,
+            scale,
+            rotation: 0.0,
+        };
+        let polylines = koch_in_view(corners, 4, &KochParams::default(), &camera, width, height);
+        let segments: Vec<f32> = polylines
+            .iter()
+            .flat_map(|points| points.windows(2).map(|pair| pair[0].distance(pair[1])))
+            .collect();
+
+        // Deep enough for as much detail as depth 4 has unzoomed, where that is a few
+        // hundred pixels a segment, without generating the curve at depth 20 and more
+        assert!(!segments.is_empty(), "nothing in view at {scale}");
+        assert!(
+            segments.len() < 20_000,
+            "{} segments at {scale}",
+            segments.len()
+        );
+        let longest = segments.iter().copied().fold(0.0, f32::max);
+        assert!(longest < 400.0, "segments of {longest} pixels at {scale}");
+        assert!(segments.iter().all(|length| *length > 0.0));
+    }
+}
@@ -0,0 +1,85 @@
+use fractal_geometry::budget::Estimate;
+use fractal_geometry::cantor::{CantorRule, Dimension, Mask, Measure};
+
+fn close(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-9
+}
+
+fn mask(mask: &str, widths: Vec<f32>) -> CantorRule {
+    CantorRule::Mask {
+        mask: mask.parse().unwrap(),
+        widths,
+    }
+}
+
+#[test]
+fn masks_keep_the_marked_parts() {
+    let rows = CantorRule::default().rows(4);
+    assert_eq!(rows.len(), 5);
+    for (split, row) in rows.iter().enumerate() {
+        assert_eq!(row.len(), 1 << split);
+        assert!(row
+            .iter()
+            .all(|(start, end)| close(end - start, 3f64.powi(-(split as i32)))));
+    }
+
+    let fifths: Vec<_> = mask("10101", vec![]).rows(1).pop().unwrap();
+    let expected = [(0.0, 0.2), (0.4, 0.6), (0.8, 1.0)];
+    assert!(fifths
+        .iter()
+        .zip(expected)
+        .all(|(a, b)| close(a.0, b.0) && close(a.1, b.1)));
+
+    let uneven = mask("101", vec![1.0, 2.0, 1.0]).rows(1).pop().unwrap();
+    assert_eq!(uneven, [(0.0, 0.25), (0.75, 1.0)]);
+    assert_eq!(mask("1001", vec![]).copies(), 2);
+}
+
+#[test]
+fn the_fat_cantor_set_keeps_half_its_length() {
+    let rows = CantorRule::SmithVolterra.rows(12);
+    assert_eq!(rows[1], [(0.0, 0.375), (0.625, 1.0)]);
+    let length: f64 = rows[12].iter().map(|(start, end)| end - start).sum();
+    assert!(length > 0.5 && length < 0.501, "{length}");
+}
+
+#[test]
+fn dust_pairs_up_the_intervals() {
+    let rule = CantorRule::default();
+    let dust = rule.dust(3);
+    assert_eq!(dust.len(), 64);
+    assert!(dust
+        .iter()
+        .all(|(min, max)| close(max.x - min.x, 1.0 / 27.0) && close(max.y - min.y, 1.0 / 27.0)));
+    assert_eq!(Estimate::cantor_dust(&rule, 3).vertices, 64 * 4);
+
+    let lines: usize = rule.rows(5).iter().map(Vec::len).sum();
+    assert_eq!(Estimate::cantor(&rule, 5).segments as usize, lines);
+}
+
+#[test]
+fn bad_masks_are_errors() {
+    assert!("1021".parse::<Mask>().is_err());
+    assert!("1".parse::<Mask>().is_err());
+    assert_eq!("1001".parse::<Mask>().unwrap().to_string(), "1001");
+    assert!(mask("000", vec![]).validate().is_err());
+    assert!(mask("101", vec![1.0, 2.0]).validate().is_err());
+    assert!(mask("101", vec![1.0, 0.0, 1.0]).validate().is_err());
+}
+
+#[test]
+fn the_rows_measure_out_the_dimension() {
+    let rows = CantorRule::default().rows(4);
+    let last = Measure::of(&rows[4]);
+    assert_eq!(last.segments, 16);
+    assert!(close(last.length, (2.0f64 / 3.0).powi(4)));
+    assert!(Dimension::of(&rows[..1]).is_none());
+
+    let dimension = Dimension::of(&rows).unwrap();
+    assert!(close(dimension.value(), 2f64.ln() / 3f64.ln()));
+    assert_eq!(dimension.to_string(), "log 2 / log 3 = 0.6309");
+
+    // The fat Cantor set keeps a positive length, so its dimension tends to 1
+    let fat = Dimension::of(&CantorRule::SmithVolterra.rows(12)).unwrap();
+    assert!(fat.value() > 0.9, "{}", fat.value());
+}
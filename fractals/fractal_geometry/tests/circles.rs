@@ -0,0 +1,111 @@
+use fractal_geometry::budget::Estimate;
+use fractal_geometry::circles::{circles, BranchPreset, BranchShape, Branching};
+use fractal_geometry::{pt2, Point2};
+
+fn close(a: Point2, b: Point2) -> bool {
+    a.distance(b) < 1e-3
+}
+
+#[test]
+fn the_presets_cover_the_old_variants() {
+    let four = BranchPreset::Four.branching();
+    let branches = four.branches(pt2(0.0, 0.0), 64.0, u32::MAX);
+    assert_eq!(branches.len(), circles(pt2(0.0, 0.0), 64.0).len());
+    // 64, 32 and 16 branch, 8 is as small as they get
+    assert_eq!(branches.len(), 1 + 4 + 16 + 64);
+    assert_eq!(branches[1].center, pt2(32.0, 0.0));
+    assert_eq!(
+        branches[2].center,
+        pt2(48.0, 0.0),
+        "depth first, like `circles`"
+    );
+
+    // The commented out two-child variant, down to a radius of 2
+    let two = BranchPreset::Two.branching();
+    let radii: Vec<f32> = two
+        .branches(pt2(0.0, 0.0), 8.0, 10)
+        .iter()
+        .map(|b| b.radius)
+        .collect();
+    assert_eq!(radii, [8.0, 4.0, 2.0, 2.0, 4.0, 2.0, 2.0]);
+    assert_eq!(two.branches(pt2(0.0, 0.0), 8.0, 1).len(), 3, "depth limit");
+}
+
+#[test]
+fn apollonian_children_touch_each_other_and_their_parent() {
+    let apollonian = BranchPreset::Apollonian.branching();
+    let branches = apollonian.branches(pt2(0.0, 0.0), 100.0, 1);
+    let [parent, a, b, c] = branches[..] else {
+        panic!("expected three children, got {branches:?}");
+    };
+    for child in [a, b, c] {
+        assert!((child.center.length() + child.radius - parent.radius).abs() < 1e-3);
+    }
+    assert!((a.center.distance(b.center) - 2.0 * a.radius).abs() < 1e-3);
+    assert!((b.center.distance(c.center) - 2.0 * a.radius).abs() < 1e-3);
+}
+
+#[test]
+fn vicsek_squares_tile_a_cross() {
+    let vicsek = BranchPreset::Vicsek.branching();
+    let branches = vicsek.branches(pt2(0.0, 0.0), 90.0, 1);
+    assert_eq!(branches.len(), 6);
+    let outline = |i: usize| vicsek.shape.outline(&branches[i]).unwrap();
+    // The middle child shares its right side with the child to its right
+    let (middle, right) = (outline(1), outline(2));
+    assert!(close(middle[0], right[1]) && close(middle[3], right[2]));
+    assert!(
+        (right[0].x - outline(0)[0].x).abs() < 1e-3,
+        "flush with the parent's side"
+    );
+    assert_eq!(BranchShape::Circle.outline(&branches[0]), None);
+}
+
+#[test]
+fn rotation_turns_children_and_their_placement() {
+    let branching = Branching {
+        angles: vec![0.0],
+        rotation: 90.0,
+        shape: BranchShape::Polygon(3),
+        ..BranchPreset::Two.branching()
+    };
+    let branches = branching.branches(pt2(0.0, 0.0), 80.0, 2);
+    assert!(close(branches[1].center, pt2(40.0, 0.0)));
+    assert!(
+        close(branches[2].center, pt2(40.0, 20.0)),
+        "turned a quarter"
+    );
+    let triangle = branching.shape.outline(&branches[1]).unwrap();
+    assert!(close(triangle[0], pt2(0.0, 0.0)), "the corner points left");
+}
+
+#[test]
+fn estimates_count_what_the_branches_draw() {
+    for preset in BranchPreset::ALL {
+        let branching = preset.branching();
+        let branches = branching.branches(pt2(0.0, 0.0), 300.0, 5);
+        let sides = branching.shape.sides() as usize;
+        let estimate = Estimate::branching(&branching, 300.0, 5);
+        assert_eq!(
+            estimate.segments as usize,
+            branches.len() * sides,
+            "{preset}"
+        );
+        assert_eq!(preset.to_string().parse::<BranchPreset>(), Ok(preset));
+    }
+}
+
+#[test]
+fn bad_rules_are_errors() {
+    let rules = |change: fn(&mut Branching)| {
+        let mut branching = BranchPreset::Four.branching();
+        change(&mut branching);
+        branching.validate()
+    };
+    assert!(rules(|_| {}).is_ok());
+    assert!(rules(|b| b.ratio = 1.0).is_err());
+    assert!(rules(|b| b.angles.clear()).is_err());
+    assert!(rules(|b| b.min_radius = 0.0).is_err());
+    assert!(rules(|b| b.shape = BranchShape::Polygon(2)).is_err());
+    assert!("spiral".parse::<BranchPreset>().is_err());
+}
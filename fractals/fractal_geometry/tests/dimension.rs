@@ -0,0 +1,56 @@
+use fractal_geometry::dimension::{BoxCounting, Fit, Theory};
+use fractal_geometry::drawing::Shape;
+use fractal_geometry::koch::{koch_line, KochType};
+use fractal_geometry::pt2;
+use glam::DVec2;
+
+#[test]
+fn fits_go_through_points_on_a_line() {
+    let fit = Fit::of(&[(0.0, 1.0), (1.0, 3.0), (2.0, 5.0)]).unwrap();
+    assert!((fit.slope - 2.0).abs() < 1e-12);
+    assert!((fit.intercept - 1.0).abs() < 1e-12);
+    assert!((fit.r_squared - 1.0).abs() < 1e-12);
+    assert_eq!(Fit::of(&[(1.0, 1.0), (1.0, 2.0)]), None);
+}
+
+#[test]
+fn lines_count_as_one_and_grids_as_two() {
+    let line = BoxCounting::of(&[Shape::Line(pt2(0.0, 0.0), pt2(512.0, 0.0))]);
+    // A single segment has nothing finer than itself to count
+    assert_eq!(line, None);
+    let steps: Vec<_> = (0..=64).map(|i| pt2(i as f32 * 8.0, 0.0)).collect();
+    let line = BoxCounting::of(&[Shape::Polyline(steps)]).unwrap();
+    assert!((line.dimension() - 1.0).abs() < 0.02, "{line}");
+
+    let grid: Vec<DVec2> = (0..256 * 256)
+        .map(|i| DVec2::new((i % 256) as f64, (i / 256) as f64))
+        .collect();
+    let sizes = [64.0, 32.0, 16.0, 8.0, 4.0];
+    let grid = BoxCounting::new(&grid, &sizes).unwrap();
+    assert!((grid.dimension() - 2.0).abs() < 1e-9, "{grid}");
+    assert_eq!(grid.counts[0], (64.0, 16));
+}
+
+#[test]
+fn koch_curves_come_close_to_their_theory() {
+    let curve = koch_line(pt2(-400.0, 0.0), pt2(400.0, 0.0), 6, KochType::Linear);
+    let estimate = BoxCounting::of(&[Shape::Polyline(curve)]).unwrap();
+    let theory = Theory::copies(vec![1.0 / 3.0; 4]);
+    assert_eq!(theory.to_string(), "log 4 / log 3 = 1.2619");
+    assert!(
+        (estimate.dimension() - theory.value()).abs() < 0.03,
+        "{estimate}"
+    );
+}
+
+#[test]
+fn uneven_copies_solve_for_the_dimension() {
+    let even = Theory::Ratios(vec![1.0 / 3.0; 4]);
+    assert!((even.value() - 4f64.ln() / 3f64.ln()).abs() < 1e-9);
+
+    // Two thirds and a third only fill the line they came from
+    let uneven = Theory::copies(vec![2.0 / 3.0, 1.0 / 3.0]);
+    assert!(matches!(uneven, Theory::Ratios(_)));
+    assert!((uneven.value() - 1.0).abs() < 1e-9);
+    assert!(uneven.to_string().ends_with("D = 1.0000"), "{uneven}");
+}
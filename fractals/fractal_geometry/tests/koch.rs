@@ -0,0 +1,147 @@
+use fractal_geometry::koch::{
+    koch_line, koch_line_into, koch_line_with, koch_radial, point_count, Bump, KochParams,
+    KochType, RadialLayout,
+};
+use fractal_geometry::pt2;
+
+#[test]
+fn inward_bumps_mirror_outward_ones() {
+    let (start, end) = (pt2(-400.0, 0.0), pt2(400.0, 0.0));
+    let outward = koch_line_with(start, end, 3, KochType::Linear, &KochParams::default());
+    let inward = koch_line_with(
+        start,
+        end,
+        3,
+        KochType::Linear,
+        &KochParams::anti_snowflake(),
+    );
+
+    for (o, i) in outward.iter().zip(&inward) {
+        assert!(o.distance(pt2(i.x, -i.y)) < 1e-3);
+    }
+}
+
+#[test]
+fn cesaro_segments_all_have_the_same_length() {
+    let params = KochParams::cesaro(85.0);
+    let points = koch_line_with(pt2(0.0, 0.0), pt2(100.0, 0.0), 1, KochType::Linear, &params);
+
+    let lengths: Vec<f32> = points
+        .windows(2)
+        .map(|pair| pair[0].distance(pair[1]))
+        .collect();
+    for length in &lengths {
+        assert!((length - lengths[0]).abs() < 1e-3, "{lengths:?}");
+    }
+    // The bump points down, below the line
+    assert!(points[2].y < 0.0);
+}
+
+#[test]
+fn alternating_bumps_flip_every_level() {
+    let params = KochParams {
+        bump: Bump::Alternating,
+        ..KochParams::default()
+    };
+    let points = koch_line_with(pt2(0.0, 0.0), pt2(90.0, 0.0), 2, KochType::Linear, &params);
+
+    // Top-level apex points up, the first second-level apex (on a flat part) points down
+    assert!(points[8].y > 0.0);
+    assert!(points[2].y < 0.0);
+}
+
+#[test]
+fn validation_rejects_degenerate_generators() {
+    assert!(KochParams::default().validate().is_ok());
+    let flat = KochParams {
+        angle: 90.0,
+        ..KochParams::default()
+    };
+    assert!(flat.validate().is_err());
+    let no_middle = KochParams {
+        ratios: [0.5, 0.0, 0.5],
+        ..KochParams::default()
+    };
+    assert!(no_middle.validate().is_err());
+}
+
+#[test]
+fn radial_follows_start_and_end() {
+    let (start, end) = (pt2(-100.0, 50.0), pt2(100.0, 50.0));
+    let points = koch_line(start, end, 2, KochType::Radial(6));
+
+    assert!(points[0].distance(start) < 1e-3);
+    assert!(points.last().unwrap().distance(start) < 1e-3);
+    // Outward bumps point into the circle, so nothing leaves it
+    let center = pt2(0.0, 50.0);
+    assert!(points.iter().all(|p| p.distance(center) <= 100.0 + 1e-3));
+}
+
+#[test]
+fn star_polygons_split_into_loops() {
+    let params = KochParams::default();
+    let pentagram = koch_radial(&RadialLayout::new(5).star(2), 0, &params);
+    assert_eq!(pentagram.len(), 1);
+    assert_eq!(pentagram[0].len(), 6);
+    // The second vertex is two steps around the circle
+    let vertex = pt2((144.0f32).to_radians().cos(), (144.0f32).to_radians().sin()) * 300.0;
+    assert!(pentagram[0][1].distance(vertex) < 1e-2);
+
+    let hexagram = koch_radial(&RadialLayout::new(6).star(2), 1, &params);
+    assert_eq!(hexagram.len(), 2);
+    assert!(hexagram.iter().all(|triangle| triangle.len() == 3 * 4 + 1));
+}
+
+#[test]
+fn per_side_depths_repeat_around_the_shape() {
+    let layout = RadialLayout::new(4).depths(vec![0, 2]);
+    let points = koch_radial(&layout, 5, &KochParams::default()).concat();
+    assert_eq!(points.len(), 1 + 1 + 16 + 1 + 16);
+}
+
+#[test]
+fn fitted_layouts_stay_inside_the_box() {
+    let center = pt2(150.0, -40.0);
+    for params in [KochParams::default(), KochParams::anti_snowflake()] {
+        let layout = RadialLayout::new(7)
+            .rotation(10.0)
+            .fit_into(center, 200.0, 120.0, 3, &params);
+        let points = koch_radial(&layout, 3, &params).concat();
+
+        let reach = points
+            .iter()
+            .map(|p| p.distance(center))
+            .fold(0.0, f32::max);
+        assert!((reach - 60.0).abs() < 1e-2, "{reach}");
+    }
+
+    // Deeper than the fit measures, the rest of the curve is only bounded
+    for params in [KochParams::default(), KochParams::cesaro(85.0)] {
+        let layout = RadialLayout::new(5).fit_into(center, 200.0, 120.0, 8, &params);
+        let reach = koch_radial(&layout, 8, &params)
+            .concat()
+            .iter()
+            .map(|p| p.distance(center))
+            .fold(0.0, f32::max);
+        assert!(reach <= 60.0 + 1e-3 && reach > 59.0, "{reach}");
+    }
+}
+
+#[test]
+fn buffers_are_reused_at_their_exact_size() {
+    let params = KochParams::default();
+    let (start, end) = (pt2(-300.0, 0.0), pt2(300.0, 0.0));
+    let mut points = Vec::new();
+
+    koch_line_into(&mut points, start, end, 5, KochType::Radial(12), &params);
+    assert_eq!(points.len(), point_count(5, 12));
+    let capacity = points.capacity();
+
+    // Shallower curves fit in the same allocation and match a freshly built one
+    koch_line_into(&mut points, start, end, 3, KochType::Linear, &params);
+    assert_eq!(points.len(), point_count(3, 1));
+    assert_eq!(points.capacity(), capacity);
+    assert_eq!(points, koch_line(start, end, 3, KochType::Linear));
+
+    assert_eq!(point_count(40, 12), usize::MAX);
+}
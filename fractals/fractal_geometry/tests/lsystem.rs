@@ -0,0 +1,91 @@
+use fractal_geometry::fit::fit_between;
+use fractal_geometry::koch::{koch_line, KochType};
+use fractal_geometry::lsystem::{LSystem, Preset, Rule};
+use fractal_geometry::pt2;
+
+#[test]
+fn koch_preset_matches_koch_line() {
+    let (start, end) = (pt2(-400.0, 0.0), pt2(400.0, 0.0));
+    for depth in 0..=5 {
+        let expected = koch_line(start, end, depth, KochType::Linear);
+        let polylines = Preset::Koch.lsystem().polylines(depth, pt2(0.0, 0.0));
+        let actual = &fit_between(&polylines, start, end)[0];
+
+        assert_eq!(actual.len(), expected.len());
+        for (a, e) in actual.iter().zip(&expected) {
+            assert!(a.distance(*e) < 1e-2, "depth {depth}: {a} != {e}");
+        }
+    }
+}
+
+#[test]
+fn expansion_applies_every_rule_at_once() {
+    let dragon = Preset::Dragon.lsystem();
+    assert_eq!(dragon.expand(0), "F");
+    assert_eq!(dragon.expand(1), "F+G");
+    assert_eq!(dragon.expand(2), "F+G+F-G");
+}
+
+#[test]
+fn closing_bracket_starts_a_new_stroke() {
+    // The branch carries on from the trunk, then `]` jumps back and starts over
+    let polylines = LSystem::new("F[+F]F", 90.0).polylines(0, pt2(0.0, 0.0));
+    assert_eq!(polylines.len(), 2);
+    assert_eq!(polylines[0].len(), 3);
+    assert!(polylines[0][2].distance(pt2(1.0, 1.0)) < 1e-6);
+    assert_eq!(polylines[1], vec![pt2(1.0, 0.0), pt2(2.0, 0.0)]);
+}
+
+#[test]
+fn every_preset_draws_something() {
+    for preset in Preset::ALL {
+        let polylines = preset.lsystem().polylines(3, pt2(0.0, 0.0));
+        assert!(!polylines.is_empty(), "{preset} drew nothing");
+        assert_eq!(preset.name().parse::<Preset>(), Ok(preset));
+    }
+}
+
+#[test]
+fn same_seed_grows_the_same_system() {
+    let bush = Preset::Bush.lsystem();
+    assert_eq!(
+        bush.clone().seed(7).expand(4),
+        bush.clone().seed(7).expand(4)
+    );
+    assert_ne!(bush.clone().seed(7).expand(4), bush.seed(8).expand(4));
+}
+
+#[test]
+fn weights_pick_between_rules() {
+    let never = LSystem::new("AAAA", 90.0)
+        .add_rule(Rule::new('A', "B").weight(1.0))
+        .add_rule(Rule::new('A', "C").weight(0.0));
+    assert_eq!(never.expand(1), "BBBB");
+
+    // No odds at all falls back to the first rule instead of panicking
+    let zero = LSystem::new("AA", 90.0)
+        .add_rule(Rule::new('A', "B").weight(0.0))
+        .add_rule(Rule::new('A', "C").weight(0.0));
+    assert!(zero.validate().is_ok());
+    assert_eq!(zero.expand(2), "BB");
+
+    let negative = never.add_rule(Rule::new('A', "D").weight(-1.0));
+    assert!(negative.validate().is_err());
+    assert_eq!(negative.expand(1), "BBBB");
+    let nan = LSystem::new("A", 90.0).add_rule(Rule::new('A', "B").weight(f32::NAN));
+    assert!(nan.validate().is_err());
+}
+
+#[test]
+fn context_rules_need_their_neighbours() {
+    // A signal `B` moves one step to the right per iteration
+    let signal = LSystem::new("BAAA", 90.0)
+        .add_rule(Rule::new('A', "B").left('B'))
+        .rule('B', "A");
+    assert_eq!(signal.expand(1), "ABAA");
+    assert_eq!(signal.expand(2), "AABA");
+
+    // Turns and side branches are not neighbours
+    let branching = LSystem::new("B+[C]A", 90.0).add_rule(Rule::new('A', "D").left('B'));
+    assert_eq!(branching.expand(1), "B+[C]D");
+}
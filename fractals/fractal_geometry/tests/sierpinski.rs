@@ -0,0 +1,122 @@
+use fractal_geometry::budget::Estimate;
+use fractal_geometry::sierpinski::{sierpinski_triangles, Sierpinski, SierpinskiShape};
+use fractal_geometry::{pt2, Point2};
+
+fn close(a: Point2, b: Point2) -> bool {
+    a.distance(b) < 1e-3
+}
+
+#[test]
+fn the_triangle_matches_sierpinski_triangles() {
+    let (width, height) = (800.0, 600.0);
+    let triangle =
+        Sierpinski::new(SierpinskiShape::Triangle).fit_into(pt2(0.0, 0.0), width, height);
+    let polygons = triangle.polygons(4);
+    let triangles = sierpinski_triangles(
+        pt2(-width / 2.0, -height / 2.0),
+        pt2(0.0, height / 2.0),
+        pt2(width / 2.0, -height / 2.0),
+        4,
+    );
+
+    assert_eq!(polygons.len(), triangles.len());
+    for polygon in &polygons {
+        let same =
+            |triangle: &&[Point2; 3]| polygon.iter().zip(*triangle).all(|(a, b)| close(*a, *b));
+        assert!(triangles.iter().any(|t| same(&t)), "{polygon:?}");
+    }
+}
+
+#[test]
+fn every_shape_splits_into_touching_copies() {
+    let cases = [
+        (SierpinskiShape::Triangle, 3, 3),
+        (SierpinskiShape::Carpet, 8, 4),
+        (SierpinskiShape::Flake(4), 4, 4),
+        (SierpinskiShape::Flake(5), 6, 5),
+        (SierpinskiShape::Flake(6), 7, 6),
+    ];
+    for (shape, copies, sides) in cases {
+        let polygons = Sierpinski::new(shape).polygons(2);
+        assert_eq!(polygons.len(), copies * copies, "{shape:?}");
+        assert!(polygons.iter().all(|polygon| polygon.len() == sides));
+        let estimate = Estimate::sierpinski(shape, 2);
+        assert_eq!(estimate.vertices as usize, copies * copies * sides);
+    }
+
+    // Neighbouring corner copies of a pentaflake share a corner
+    let flake = Sierpinski::new(SierpinskiShape::Flake(5)).polygons(1);
+    let shared = flake[0]
+        .iter()
+        .any(|a| flake[1].iter().any(|b| close(*a, *b)));
+    assert!(shared, "{flake:?}");
+    assert!(SierpinskiShape::Flake(2).validate().is_err());
+}
+
+#[test]
+fn equilateral_shapes_keep_their_proportions() {
+    let box_ = |shape| Sierpinski::new(shape).fit_into(pt2(10.0, 0.0), 800.0, 600.0);
+
+    let stretched = box_(SierpinskiShape::Triangle).polygons(0).remove(0);
+    assert!(close(stretched[2], pt2(10.0, 300.0)));
+
+    let triangle = box_(SierpinskiShape::Triangle)
+        .equilateral(true)
+        .polygons(0)
+        .remove(0);
+    let sides: Vec<f32> = (0..3)
+        .map(|i| triangle[i].distance(triangle[(i + 1) % 3]))
+        .collect();
+    assert!((sides[0] - sides[1]).abs() < 1e-3 && (sides[1] - sides[2]).abs() < 1e-3);
+    assert!(triangle
+        .iter()
+        .all(|p| p.y.abs() <= 300.001 && (p.x - 10.0).abs() <= 400.001));
+
+    let square = box_(SierpinskiShape::Carpet)
+        .equilateral(true)
+        .polygons(0)
+        .remove(0);
+    assert!(close(square[0], pt2(-290.0, -300.0)));
+}
+
+#[test]
+fn the_chaos_game_stays_out_of_the_holes() {
+    let triangle = Sierpinski::new(SierpinskiShape::Triangle)
+        .equilateral(true)
+        .fit_into(pt2(0.0, 0.0), 600.0, 600.0);
+    let points = triangle.chaos_game(10_000, 7);
+    assert_eq!(points.len(), 10_000);
+    assert_eq!(points, triangle.chaos_game(10_000, 7));
+
+    // The hole in the middle is the triangle between the midpoints of the sides, a
+    // little smaller so that points on its edges do not count
+    let corners = &triangle.polygons(0)[0];
+    let middle = (corners[0] + corners[1] + corners[2]) / 3.0;
+    let hole: Vec<Point2> = (0..3)
+        .map(|i| (corners[i] + corners[(i + 1) % 3]) / 2.0)
+        .map(|point| middle + (point - middle) * 0.99)
+        .collect();
+    let inside = |p: Point2| {
+        (0..3).all(|i| {
+            let (a, b) = (hole[i], hole[(i + 1) % 3]);
+            (b - a).perp_dot(p - a) > 0.0
+        })
+    };
+    assert!(!points.iter().any(|p| inside(*p)));
+}
+
+#[test]
+fn pieces_come_before_the_copies_they_split_into() {
+    let triangle = Sierpinski::new(SierpinskiShape::Triangle);
+    let pieces = triangle.pieces(2);
+    assert_eq!(pieces.len(), 1 + 3 + 9);
+    let levels: Vec<u32> = pieces.iter().take(5).map(|piece| piece.level).collect();
+    assert_eq!(levels, [0, 1, 2, 2, 2]);
+
+    let leaves: Vec<_> = pieces
+        .into_iter()
+        .filter(|piece| piece.leaf)
+        .map(|piece| piece.points)
+        .collect();
+    assert_eq!(leaves, triangle.polygons(2));
+}
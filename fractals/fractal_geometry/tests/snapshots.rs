@@ -0,0 +1,143 @@
+//! Pins the exact output of every generator so a refactor of the recursion cannot
+//! silently change the art.
+//!
+//! Snapshots live in `tests/snapshots`, one row of numbers per point (or per shape).
+//! Run with `UPDATE_SNAPSHOTS=1` to rewrite them after an intentional change.
+
+use std::fs;
+use std::path::PathBuf;
+
+use fractal_geometry::cantor::cantor;
+use fractal_geometry::circles::circles;
+use fractal_geometry::koch::{self, koch_line, KochParams, KochType, RadialLayout};
+use fractal_geometry::pt2;
+use fractal_geometry::sierpinski::sierpinski_triangles;
+
+// Allows for last-digit differences in `sin`/`cos` between platforms
+const TOLERANCE: f32 = 1e-3;
+
+fn assert_snapshot(name: &str, rows: Vec<Vec<f32>>) {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/snapshots")
+        .join(format!("{name}.txt"));
+    let actual: String = rows
+        .iter()
+        .map(|row| {
+            let row: Vec<String> = row.iter().map(|value| value.to_string()).collect();
+            row.join(" ") + "\n"
+        })
+        .collect();
+
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, actual).unwrap();
+        return;
+    }
+
+    let expected = fs::read_to_string(&path)
+        .unwrap_or_else(|_| panic!("missing snapshot {path:?}, run with UPDATE_SNAPSHOTS=1"));
+    let expected: Vec<Vec<f32>> = expected
+        .lines()
+        .map(|line| {
+            line.split(' ')
+                .map(|value| value.parse().unwrap())
+                .collect()
+        })
+        .collect();
+
+    assert_eq!(rows.len(), expected.len(), "{name}: row count changed");
+    for (i, (actual, expected)) in rows.iter().zip(&expected).enumerate() {
+        assert_eq!(
+            actual.len(),
+            expected.len(),
+            "{name}: row {i} changed shape"
+        );
+        for (a, e) in actual.iter().zip(expected) {
+            assert!(
+                (a - e).abs() <= TOLERANCE,
+                "{name}: row {i} is {actual:?}, expected {expected:?}"
+            );
+        }
+    }
+}
+
+#[test]
+fn koch_linear() {
+    for depth in 0..=4 {
+        let points = koch_line(pt2(-400.0, 0.0), pt2(400.0, 0.0), depth, KochType::Linear);
+        assert_eq!(points.len(), 4usize.pow(depth) + 1);
+        let rows = points.iter().map(|p| vec![p.x, p.y]).collect();
+        assert_snapshot(&format!("koch_linear_{depth}"), rows);
+    }
+}
+
+#[test]
+fn koch_radial() {
+    // `RadialLayout::new` keeps the original radius of 300 around the origin, so these
+    // are still the curves the first radial sketches drew
+    for (sides, depth) in [(3, 0), (3, 1), (3, 3), (12, 2)] {
+        let layout = RadialLayout::new(sides);
+        let points = koch::koch_radial(&layout, depth, &KochParams::default()).concat();
+        assert_eq!(points.len(), 4usize.pow(depth) * sides as usize + 1);
+        let rows = points.iter().map(|p| vec![p.x, p.y]).collect();
+        assert_snapshot(&format!("koch_radial_{sides}_{depth}"), rows);
+    }
+}
+
+#[test]
+fn koch_line_radial() {
+    // Off-center, so the circle has to follow `start` and `end`
+    for (sides, depth) in [(3, 1), (6, 2)] {
+        let points = koch_line(
+            pt2(-100.0, 50.0),
+            pt2(300.0, 50.0),
+            depth,
+            KochType::Radial(sides),
+        );
+        assert_eq!(points.len(), 4usize.pow(depth) * sides as usize + 1);
+        let rows = points.iter().map(|p| vec![p.x, p.y]).collect();
+        assert_snapshot(&format!("koch_line_radial_{sides}_{depth}"), rows);
+    }
+}
+
+#[test]
+fn sierpinski() {
+    for depth in 0..=4 {
+        let triangles = sierpinski_triangles(
+            pt2(-512.0, -384.0),
+            pt2(0.0, 768.0),
+            pt2(512.0, -384.0),
+            depth,
+        );
+        assert_eq!(triangles.len(), 3usize.pow(depth));
+        let rows = triangles
+            .iter()
+            .map(|t| t.iter().flat_map(|p| [p.x, p.y]).collect())
+            .collect();
+        assert_snapshot(&format!("sierpinski_{depth}"), rows);
+    }
+}
+
+#[test]
+fn cantor_bars() {
+    for len in [27.0, 81.0, 243.0] {
+        let segments = cantor(-len / 2.0, -100.0, len);
+        let rows = segments
+            .iter()
+            .map(|(start, end)| vec![start.x, start.y, end.x, end.y])
+            .collect();
+        assert_snapshot(&format!("cantor_{len}"), rows);
+    }
+}
+
+#[test]
+fn circle_recursion() {
+    for radius in [20.0, 40.0, 80.0] {
+        let circles = circles(pt2(51.2, 38.4), radius);
+        let rows = circles
+            .iter()
+            .map(|c| vec![c.center.x, c.center.y, c.radius])
+            .collect();
+        assert_snapshot(&format!("circles_{radius}"), rows);
+    }
+}
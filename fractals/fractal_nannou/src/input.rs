@@ -0,0 +1,103 @@
+//! Keyboard and mouse controls shared by every sketch with a `SceneView`:
+//!
+//! - up and down go one level deeper or shallower
+//! - left and right take a side from or add one to a radial curve
+//! - `T` switches to the next kind of fractal
+//! - the mouse wheel zooms about the cursor and dragging pans
+//! - `R` goes back to the scene the sketch started with, `S` saves the scene
+//! - `H` hides or shows the settings and these controls at the bottom of the window
+
+use fractal_scene::{Edit, Scene};
+use nannou::prelude::*;
+
+const HELP: &str =
+    "up/down depth, left/right sides, T type, wheel zoom, drag pan, R reset, S save, H hide";
+
+// Each notch of the mouse wheel zooms by this much
+const ZOOM_STEP: f32 = 1.1;
+// Trackpads scroll in pixels, about this many to a notch
+const PIXELS_PER_NOTCH: f32 = 40.0;
+
+/// Where the mouse is, and whether it is dragging the scene around.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub(crate) struct Pointer {
+    position: Point2,
+    dragging: bool,
+}
+
+/// What a window event asks of the scene.
+pub(crate) enum Command {
+    Edit(Edit),
+    Reset,
+    Save,
+    ToggleHud,
+}
+
+impl Pointer {
+    pub(crate) fn command(&mut self, event: &WindowEvent) -> Option<Command> {
+        match event {
+            WindowEvent::KeyPressed(key) => key_command(*key),
+            WindowEvent::MousePressed(MouseButton::Left) => {
+                self.dragging = true;
+                None
+            }
+            WindowEvent::MouseReleased(MouseButton::Left) => {
+                self.dragging = false;
+                None
+            }
+            WindowEvent::MouseMoved(position) => {
+                let moved = *position - self.position;
+                self.position = *position;
+                self.dragging.then_some(Command::Edit(Edit::Pan(moved)))
+            }
+            WindowEvent::MouseWheel(delta, _) => {
+                let notches = match delta {
+                    MouseScrollDelta::LineDelta(_, y) => *y,
+                    MouseScrollDelta::PixelDelta(position) => position.y as f32 / PIXELS_PER_NOTCH,
+                };
+                Some(Command::Edit(Edit::Zoom {
+                    factor: ZOOM_STEP.powf(notches),
+                    about: self.position,
+                }))
+            }
+            _ => None,
+        }
+    }
+}
+
+fn key_command(key: Key) -> Option<Command> {
+    let edit = match key {
+        Key::Up => Edit::Depth(1),
+        Key::Down => Edit::Depth(-1),
+        Key::Left => Edit::Sides(-1),
+        Key::Right => Edit::Sides(1),
+        Key::T => Edit::NextFractal,
+        Key::R => return Some(Command::Reset),
+        Key::S => return Some(Command::Save),
+        Key::H => return Some(Command::ToggleHud),
+        _ => return None,
+    };
+    Some(Command::Edit(edit))
+}
+
+// The scene's settings and the controls, in the bottom left corner
+pub(crate) fn draw_hud(draw: &Draw, window: Rect, scene: &Scene) {
+    let text = format!(
+        "{} | depth {} | zoom {:.2}\n{HELP}",
+        scene.fractal, scene.depth, scene.camera.scale
+    );
+    let height = 44.0;
+    let y = window.bottom() + height / 2.0;
+
+    draw.rect()
+        .x_y(window.x(), y)
+        .w_h(window.w(), height)
+        .color(srgba(0.0, 0.0, 0.0, 0.6));
+    draw.text(&text)
+        .x_y(window.x(), y)
+        .w_h(window.w() - 20.0, height - 8.0)
+        .left_justify()
+        .align_text_top()
+        .font_size(13)
+        .color(WHITE);
+}
@@ -0,0 +1,94 @@
+//! Thin nannou adapter for the geometry in `fractal_geometry`.
+
+use fractal_geometry::drawing::{Drawing, Rgb, Shape, Style};
+use nannou::prelude::*;
+
+mod input;
+mod run;
+mod scene_view;
+mod sketch;
+
+pub use run::{open_scene, run_scene};
+pub use scene_view::SceneView;
+pub use sketch::SketchConfig;
+
+// Draws consecutive points as individual line segments
+pub fn draw_polyline(draw: &Draw, points: &[Point2], stroke_weight: f32, color: Srgb<u8>) {
+    for pair in points.windows(2) {
+        draw.line()
+            .start(pair[0])
+            .end(pair[1])
+            .stroke_weight(stroke_weight)
+            .color(color);
+    }
+}
+
+// Draws a whole `Drawing`, background included
+pub fn draw_drawing(draw: &Draw, drawing: &Drawing) {
+    draw.background().color(srgb(drawing.background));
+
+    for (shape, style) in &drawing.shapes {
+        draw_shape(draw, shape, style);
+    }
+}
+
+pub fn draw_shape(draw: &Draw, shape: &Shape, style: &Style) {
+    match shape {
+        Shape::Polyline(points) => {
+            if let Some(stroke) = style.stroke {
+                draw_polyline(draw, points, style.stroke_weight, srgb(stroke));
+            }
+        }
+        Shape::Line(start, end) => {
+            if let Some(stroke) = style.stroke {
+                draw.line()
+                    .start(*start)
+                    .end(*end)
+                    .stroke_weight(style.stroke_weight)
+                    .color(srgb(stroke));
+            }
+        }
+        Shape::Tri([a, b, c]) => {
+            if let Some(fill) = style.fill {
+                draw.tri().points(*a, *b, *c).color(srgb(fill));
+            }
+            if let Some(stroke) = style.stroke {
+                draw_polyline(draw, &[*a, *b, *c, *a], style.stroke_weight, srgb(stroke));
+            }
+        }
+        Shape::Polygon(points) => {
+            if let Some(fill) = style.fill {
+                draw.polygon()
+                    .points(points.iter().copied())
+                    .color(srgb(fill));
+            }
+            if let Some(stroke) = style.stroke {
+                let closed: Vec<Point2> = points.iter().chain(points.first()).copied().collect();
+                draw_polyline(draw, &closed, style.stroke_weight, srgb(stroke));
+            }
+        }
+        Shape::Circle { center, radius } => {
+            let ellipse = draw.ellipse().xy(*center).radius(*radius);
+            let ellipse = match style.fill {
+                Some(fill) => ellipse.color(srgb(fill)),
+                None => ellipse.no_fill(),
+            };
+            if let Some(stroke) = style.stroke {
+                ellipse
+                    .stroke(srgb(stroke))
+                    .stroke_weight(style.stroke_weight);
+            }
+        }
+        Shape::Points { points, size } => {
+            if let Some(fill) = style.fill {
+                for point in points {
+                    draw.rect().xy(*point).w_h(*size, *size).color(srgb(fill));
+                }
+            }
+        }
+    }
+}
+
+pub fn srgb(color: Rgb) -> Srgb<u8> {
+    Srgb::new(color.r, color.g, color.b)
+}
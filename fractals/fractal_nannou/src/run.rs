@@ -0,0 +1,69 @@
+//! The `main` every scene sketch shares, so a sketch only says what it draws first.
+
+use fractal_scene::{LiveScene, Scene};
+use nannou::prelude::*;
+
+use crate::{SceneView, SketchConfig};
+
+/// Draws `scene` in a `window`-sized window, or writes it to files.
+///
+/// `--scene scene.toml` draws a scene file instead and redraws it whenever the file
+/// changes. `--svg out.svg` and `--png out.png` write the sketch to a file instead of
+/// opening the window, at the window's size unless `--size <w>x<h>` says otherwise. Bad
+/// arguments and failed exports print an error and exit with a non-zero code.
+pub fn run_scene(window: (u32, u32), scene: Scene) {
+    let scene = LiveScene::from_args(scene).unwrap_or_else(|err| exit(&err.to_string()));
+    let config = SketchConfig::from_args(window, scene).unwrap_or_else(|err| exit(&err));
+    let drawing = |width, height| config.params.scene().drawing(width, height);
+    match config.export_with(drawing) {
+        Ok(true) => return,
+        Ok(false) => {}
+        Err(err) => exit(&format!("failed to export: {err}")),
+    }
+
+    open_scene(config);
+}
+
+/// Opens the window for a scene, where the arrow keys, `T`, the mouse wheel and dragging
+/// change it and `S` saves it, see `SceneView`.
+pub fn open_scene(config: SketchConfig<LiveScene>) {
+    config.app(model).update(update).run();
+}
+
+fn exit(message: &str) -> ! {
+    eprintln!("error: {message}");
+    std::process::exit(1);
+}
+
+struct Model {
+    scene: SceneView,
+}
+
+fn model(app: &App, config: SketchConfig<LiveScene>) -> Model {
+    let (width, height) = config.window;
+    app.new_window()
+        .size(width, height)
+        .view(view)
+        .event(event)
+        .build()
+        .unwrap();
+    Model {
+        scene: SceneView::new(config.params),
+    }
+}
+
+fn update(app: &App, model: &mut Model, _update: Update) {
+    // Picks up edits to the `--scene` file, keeping the last good scene if it breaks
+    model.scene.update(app.window_rect());
+}
+
+// Keys and mouse change the scene, see `SceneView`
+fn event(_app: &App, model: &mut Model, event: WindowEvent) {
+    model.scene.event(&event);
+}
+
+fn view(app: &App, model: &Model, frame: Frame) {
+    let draw = app.draw();
+    model.scene.draw(&draw, app.window_rect());
+    draw.to_frame(app, &frame).unwrap();
+}
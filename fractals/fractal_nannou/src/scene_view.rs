@@ -0,0 +1,142 @@
+//! Keeps a sketch's scene drawn while its window runs, following edits to the scene file.
+
+use fractal_geometry::drawing::Drawing;
+use fractal_scene::{LiveScene, Scene};
+use nannou::prelude::*;
+
+use crate::draw_drawing;
+use crate::input::{draw_hud, Command, Pointer};
+
+/// A `LiveScene` together with its generated geometry.
+///
+/// Call `update` from the sketch's `update`, `event` from its window's `event` and `draw`
+/// from its `view`. The geometry is only generated again when the scene changes or the
+/// window is resized, and a scene file that does not parse shows up over the last good
+/// drawing until it is fixed. See `input` for the controls.
+pub struct SceneView {
+    live: LiveScene,
+    start: Scene, // What `R` goes back to, the last scene read from the file if there is one
+    pointer: Pointer,
+    hud: bool,
+    drawing: Option<Drawing>, // `None` until generated for the current scene
+    error: Option<String>,
+}
+
+impl SceneView {
+    pub fn new(live: LiveScene) -> Self {
+        SceneView {
+            start: live.scene().clone(),
+            live,
+            pointer: Pointer::default(),
+            hud: true,
+            drawing: None,
+            error: None,
+        }
+    }
+
+    pub fn scene(&self) -> &Scene {
+        self.live.scene()
+    }
+
+    pub fn update(&mut self, window: Rect) {
+        match self.live.reload() {
+            Ok(true) => {
+                self.start = self.scene().clone();
+                self.drawing = None;
+                self.error = None;
+            }
+            Ok(false) => {}
+            Err(err) => self.error = Some(err.to_string()),
+        }
+
+        let resized = self
+            .drawing
+            .as_ref()
+            .is_some_and(|drawing| (drawing.width, drawing.height) != (window.w(), window.h()));
+        if self.drawing.is_none() || resized {
+            self.drawing = Some(self.scene().drawing(window.w(), window.h()));
+        }
+    }
+
+    pub fn event(&mut self, event: &WindowEvent) {
+        match self.pointer.command(event) {
+            // Edits that do not apply or go over budget are ignored
+            Some(Command::Edit(edit)) => {
+                if let Some(scene) = self.scene().edited(edit) {
+                    self.set(scene);
+                }
+            }
+            Some(Command::Reset) => self.set(self.start.clone()),
+            Some(Command::Save) => self.save(),
+            Some(Command::ToggleHud) => self.hud = !self.hud,
+            None => {}
+        }
+    }
+
+    fn set(&mut self, scene: Scene) {
+        self.live.set(scene);
+        self.drawing = None;
+    }
+
+    /// Writes the scene back to its file, or to `scene.toml` when it has none.
+    pub fn save(&mut self) {
+        match self.live.save() {
+            Ok(path) => println!("saved {}", path.display()),
+            Err(err) => self.error = Some(format!("failed to save the scene: {err}")),
+        }
+    }
+
+    pub fn draw(&self, draw: &Draw, window: Rect) {
+        if let Some(drawing) = &self.drawing {
+            draw_drawing(draw, drawing);
+        }
+        if self.hud {
+            if let Some(overlay) = self.drawing.as_ref().and_then(|d| d.overlay.as_ref()) {
+                draw_overlay(draw, window, overlay);
+            }
+            draw_hud(draw, window, self.scene());
+        }
+        if let Some(error) = &self.error {
+            draw_error(draw, window, error);
+        }
+    }
+}
+
+// A red banner across the top of the window, tall enough for every line of `message`
+fn draw_error(draw: &Draw, window: Rect, message: &str) {
+    let lines = message.lines().count().max(1) as f32;
+    let height = 16.0 + 18.0 * lines;
+    let y = window.top() - height / 2.0;
+
+    draw.rect()
+        .x_y(window.x(), y)
+        .w_h(window.w(), height)
+        .color(srgba(0.6, 0.0, 0.0, 0.85));
+    draw.text(message)
+        .x_y(window.x(), y)
+        .w_h(window.w() - 20.0, height - 8.0)
+        .left_justify()
+        .align_text_top()
+        .font_size(14)
+        .color(WHITE);
+}
+
+// A translucent box in the top left corner, e.g. for a Cantor set's measurements
+fn draw_overlay(draw: &Draw, window: Rect, text: &str) {
+    let lines = text.lines().count().max(1) as f32;
+    let (width, height) = (280.0, 12.0 + 16.0 * lines);
+    let x = window.left() + width / 2.0;
+    let y = window.top() - height / 2.0;
+
+    draw.rect()
+        .x_y(x, y)
+        .w_h(width, height)
+        .color(srgba(0.0, 0.0, 0.0, 0.6));
+    draw.text(text)
+        .x_y(x, y)
+        .w_h(width - 16.0, height - 8.0)
+        .left_justify()
+        .align_text_top()
+        .font_size(12)
+        .color(WHITE);
+}
@@ -0,0 +1,77 @@
+//! Hands the settings a sketch's `main` worked out to its nannou model, without a global.
+
+use std::future;
+use std::io;
+
+use fractal_export::Export;
+use fractal_geometry::drawing::Drawing;
+use nannou::app::Builder;
+use nannou::App;
+
+/// Everything a sketch knows before its window opens.
+///
+/// Build it in `main`, from `from_args` or from the sketch's own argument parsing, then
+/// either `export_with` or start the window with `app`:
+///
+/// ```ignore
+/// let config = SketchConfig::from_args((800, 600), ()).unwrap_or_else(|err| {
+///     eprintln!("error: {err}");
+///     std::process::exit(1);
+/// });
+/// match config.export_with(drawing) {
+///     Ok(true) => return,
+///     Ok(false) => {}
+///     Err(err) => {
+///         eprintln!("error: failed to export: {err}");
+///         std::process::exit(1);
+///     }
+/// }
+/// config.app(model).update(update).run();
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SketchConfig<P = ()> {
+    pub window: (u32, u32), // Window size, and the export size unless `--size` is given
+    pub export: Export,
+    pub params: P, // Whatever else the sketch parsed
+}
+
+impl<P> SketchConfig<P> {
+    pub fn new(window: (u32, u32), params: P) -> Self {
+        SketchConfig {
+            window,
+            export: Export::default(),
+            params,
+        }
+    }
+
+    /// Picks up `--svg`, `--png` and `--size` from the process arguments, for sketches
+    /// that do not parse any arguments of their own. Errors say which flag is wrong.
+    pub fn from_args(window: (u32, u32), params: P) -> Result<Self, String> {
+        Ok(SketchConfig::new(window, params).export(Export::from_args()?))
+    }
+
+    pub fn export(mut self, export: Export) -> Self {
+        self.export = export;
+        self
+    }
+
+    /// Writes the files asked for on the command line, drawn by `drawing(width, height)`.
+    /// Returns whether there were any, in which case the window should not open.
+    pub fn export_with(&self, drawing: impl FnOnce(f32, f32) -> Drawing) -> io::Result<bool> {
+        if !self.export.is_requested() {
+            return Ok(false);
+        }
+        let (width, height) = self.export.size_or(self.window);
+        self.export.save(&drawing(width as f32, height as f32))?;
+        Ok(true)
+    }
+
+    /// Starts building the nannou app, with `model` receiving this config when the app
+    /// is ready instead of reading it from a global.
+    pub fn app<M: 'static>(self, model: fn(&App, SketchConfig<P>) -> M) -> Builder<M>
+    where
+        P: 'static,
+    {
+        Builder::new_async(move |app| Box::new(future::ready(model(app, self))))
+    }
+}
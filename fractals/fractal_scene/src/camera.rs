@@ -0,0 +1,43 @@
+// The camera as a scene file has it: `zoom` rather than `scale`, and its center as a
+// plain pair of numbers
+
+use fractal_geometry::camera::Camera;
+use glam::DVec2;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+#[derive(Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct CameraFile {
+    center: [f64; 2], // The point of the fractal in the middle of the window
+    zoom: f64,
+    rotation: f64, // Degrees, counter-clockwise
+}
+
+impl Default for CameraFile {
+    fn default() -> Self {
+        CameraFile::from(&Camera::default())
+    }
+}
+
+impl From<&Camera> for CameraFile {
+    fn from(camera: &Camera) -> Self {
+        CameraFile {
+            center: camera.center.into(),
+            zoom: camera.scale,
+            rotation: camera.rotation,
+        }
+    }
+}
+
+pub fn serialize<S: Serializer>(camera: &Camera, serializer: S) -> Result<S::Ok, S::Error> {
+    CameraFile::from(camera).serialize(serializer)
+}
+
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Camera, D::Error> {
+    let camera = CameraFile::deserialize(deserializer)?;
+    Ok(Camera {
+        center: DVec2::from(camera.center),
+        scale: camera.zoom,
+        rotation: camera.rotation,
+    })
+}
@@ -0,0 +1,149 @@
+use std::fmt;
+
+use fractal_geometry::lsystem::Preset;
+use fractal_geometry::{Point2, Vec2};
+
+use fractal_geometry::cantor::CantorRule;
+use fractal_geometry::circles::BranchPreset;
+use fractal_geometry::sierpinski::SierpinskiShape;
+
+use crate::{
+    Apollonian, BranchKind, Cantor, Circles, Fractal, Koch, KochKind, Render, Scene, Sierpinski,
+    SierpinskiKind,
+};
+
+/// One small change to a scene, e.g. from a key press in a running sketch.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Edit {
+    Depth(i32),
+    Sides(i32), // Only for radial Koch curves, flakes and recursive polygons
+    NextFractal,
+    Zoom { factor: f32, about: Point2 }, // `about` stays where it is in the window
+    Pan(Vec2),
+}
+
+impl Scene {
+    /// The scene with `edit` made, or `None` when the edit does not apply to this fractal
+    /// or would leave a scene that cannot be drawn, e.g. one too deep for the budget.
+    pub fn edited(&self, edit: Edit) -> Option<Scene> {
+        let mut scene = self.clone();
+        match edit {
+            Edit::Depth(by) => scene.depth = self.depth.checked_add_signed(by)?,
+            Edit::Sides(by) => match &mut scene.fractal {
+                Fractal::Koch(koch) if koch.koch_type == KochKind::Radial => {
+                    koch.sides = koch.sides.checked_add_signed(by)?;
+                }
+                Fractal::Sierpinski(sierpinski) if sierpinski.shape == SierpinskiKind::Flake => {
+                    sierpinski.sides = sierpinski.sides.checked_add_signed(by)?;
+                }
+                Fractal::Circles(circles) if circles.shape == BranchKind::Polygon => {
+                    circles.sides = circles.sides.checked_add_signed(by)?;
+                }
+                _ => return None,
+            },
+            Edit::NextFractal => {
+                scene.fractal = self.fractal.next();
+                // The same depth can cost far more in another fractal
+                while scene.validate().is_err() && scene.depth > 0 {
+                    scene.depth -= 1;
+                }
+            }
+            Edit::Zoom { factor, about } => {
+                scene.camera = self.camera.zoomed_about(f64::from(factor), about);
+            }
+            Edit::Pan(by) => scene.camera = self.camera.panned(by),
+        }
+        scene.validate().ok()?;
+        Some(scene)
+    }
+}
+
+impl Fractal {
+    /// The next kind of fractal with its default parameters, back to Koch after the last.
+    ///
+    /// Sierpinski triangles go on to the carpet and the pentaflake before the Cantor set,
+    /// and circles go through their presets before the Apollonian gasket.
+    pub fn next(&self) -> Fractal {
+        let sierpinski = |shape| Fractal::Sierpinski(Sierpinski::new(shape));
+        match self {
+            Fractal::Koch(_) => sierpinski(SierpinskiShape::Triangle),
+            Fractal::Sierpinski(fractal) => match fractal.shape {
+                SierpinskiKind::Triangle => sierpinski(SierpinskiShape::Carpet),
+                SierpinskiKind::Carpet => sierpinski(SierpinskiShape::Flake(5)),
+                SierpinskiKind::Flake => Fractal::Cantor(Cantor::default()),
+            },
+            Fractal::Cantor(_) => Fractal::Circles(Circles::default()),
+            Fractal::Circles(circles) => {
+                let next = circles.preset().and_then(|preset| {
+                    let mut presets = BranchPreset::ALL.into_iter();
+                    presets.find(|&p| p == preset);
+                    presets.next()
+                });
+                match next {
+                    Some(next) => Fractal::Circles(Circles::new(circles.radius, next.branching())),
+                    None => Fractal::Apollonian(Apollonian::default()),
+                }
+            }
+            Fractal::Apollonian(_) => Fractal::Lsystem {
+                preset: Preset::Dragon,
+                seed: 0,
+            },
+            Fractal::Lsystem { .. } => Fractal::Koch(Koch::default()),
+        }
+    }
+}
+
+// Short enough for a line of the sketches' HUD, e.g. `koch star {5/2}`
+impl fmt::Display for Fractal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Fractal::Koch(koch) => match koch.koch_type {
+                KochKind::Linear => write!(f, "koch line"),
+                KochKind::Radial if koch.star > 1 => {
+                    write!(f, "koch star {{{}/{}}}", koch.sides, koch.star)
+                }
+                KochKind::Radial => write!(f, "koch, {} sides", koch.sides),
+            },
+            Fractal::Sierpinski(sierpinski) => {
+                match sierpinski.shape {
+                    SierpinskiKind::Triangle => write!(f, "sierpinski")?,
+                    SierpinskiKind::Carpet => write!(f, "sierpinski carpet")?,
+                    SierpinskiKind::Flake => write!(f, "{}-flake", sierpinski.sides)?,
+                }
+                match (sierpinski.points, sierpinski.render) {
+                    (Some(points), _) => write!(f, ", chaos game of {points} points"),
+                    (None, Render::Filled) => Ok(()),
+                    (None, Render::Outline) => write!(f, ", outline"),
+                    (None, Render::Inverted) => write!(f, ", inverted"),
+                }
+            }
+            Fractal::Cantor(cantor) => {
+                match (cantor.fat, cantor.rule() == CantorRule::default()) {
+                    (true, _) => write!(f, "fat cantor")?,
+                    (false, true) => write!(f, "cantor")?,
+                    (false, false) => write!(f, "cantor {}", cantor.mask)?,
+                }
+                if cantor.dust {
+                    write!(f, " dust")?;
+                }
+                Ok(())
+            }
+            Fractal::Circles(circles) => match circles.preset() {
+                Some(BranchPreset::Four) => write!(f, "circles, radius {}", circles.radius),
+                Some(preset) => write!(f, "circles {preset}, radius {}", circles.radius),
+                None => write!(
+                    f,
+                    "circles, {} children, radius {}",
+                    circles.branching().children(),
+                    circles.radius
+                ),
+            },
+            Fractal::Apollonian(gasket) => {
+                let [a, b, c] = gasket.curvatures;
+                write!(f, "apollonian {a}, {b}, {c}")
+            }
+            Fractal::Lsystem { preset, seed: 0 } => write!(f, "lsystem {preset}"),
+            Fractal::Lsystem { preset, seed } => write!(f, "lsystem {preset}, seed {seed}"),
+        }
+    }
+}
@@ -0,0 +1,153 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::Scene;
+
+/// How a scene file is written, going by its extension.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Toml,
+    Json,
+}
+
+impl Format {
+    // `.json` is JSON, anything else TOML
+    pub fn of(path: &Path) -> Self {
+        match path.extension() {
+            Some(extension) if extension.eq_ignore_ascii_case("json") => Format::Json,
+            _ => Format::Toml,
+        }
+    }
+}
+
+impl Scene {
+    /// Reads and validates a scene, so a scene that parses is also one that can be drawn.
+    pub fn parse(text: &str, format: Format) -> Result<Scene, String> {
+        let scene: Scene = match format {
+            Format::Toml => toml::from_str(text).map_err(|err| err.to_string())?,
+            Format::Json => serde_json::from_str(text).map_err(|err| err.to_string())?,
+        };
+        scene.validate()?;
+        Ok(scene)
+    }
+
+    pub fn to_text(&self, format: Format) -> String {
+        match format {
+            Format::Toml => toml::to_string(self).expect("scenes always serialize"),
+            Format::Json => serde_json::to_string_pretty(self).expect("scenes always serialize"),
+        }
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Scene> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path)?;
+        Scene::parse(&text, Format::of(path)).map_err(|err| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{}: {err}", path.display()),
+            )
+        })
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        fs::write(path, self.to_text(Format::of(path)))
+    }
+}
+
+/// A sketch's scene, read again from its file whenever the file changes.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LiveScene {
+    scene: Scene,
+    path: Option<PathBuf>,
+    stamp: Option<(SystemTime, u64)>, // Modification time and length when last read
+}
+
+impl LiveScene {
+    /// Where `save` writes a scene that did not come from a file.
+    pub const DEFAULT_PATH: &'static str = "scene.toml";
+
+    /// A scene of the sketch's own, with no file behind it.
+    pub fn new(scene: Scene) -> Self {
+        LiveScene {
+            scene,
+            path: None,
+            stamp: None,
+        }
+    }
+
+    pub fn watch(path: impl Into<PathBuf>) -> io::Result<Self> {
+        let path = path.into();
+        let stamp = stamp(&path);
+        Ok(LiveScene {
+            scene: Scene::load(&path)?,
+            path: Some(path),
+            stamp,
+        })
+    }
+
+    /// Looks for `--scene <path>` among the process arguments, for sketches that do not
+    /// parse any arguments of their own, and otherwise starts from `default`.
+    pub fn from_args(default: Scene) -> io::Result<Self> {
+        let args: Vec<String> = std::env::args().collect();
+        match args.iter().position(|arg| arg == "--scene") {
+            Some(position) => match args.get(position + 1) {
+                Some(path) => LiveScene::watch(path),
+                None => Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "--scene needs a path",
+                )),
+            },
+            None => Ok(LiveScene::new(default)),
+        }
+    }
+
+    pub fn scene(&self) -> &Scene {
+        &self.scene
+    }
+
+    /// Replaces the scene, e.g. with an edit made in the window. The file is left alone
+    /// until `save`.
+    pub fn set(&mut self, scene: Scene) {
+        self.scene = scene;
+    }
+
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Reads the file again if it changed since it was last read, cheap enough to call
+    /// every frame. Returns whether it was read; a file that does not parse is an error
+    /// and leaves the previous scene in place.
+    pub fn reload(&mut self) -> io::Result<bool> {
+        let Some(path) = &self.path else {
+            return Ok(false);
+        };
+        // A file that is briefly missing is most likely being replaced by an editor
+        let stamp = stamp(path);
+        if stamp.is_none() || stamp == self.stamp {
+            return Ok(false);
+        }
+        self.stamp = stamp;
+        self.scene = Scene::load(path)?;
+        Ok(true)
+    }
+
+    /// Writes the scene back to its file, or to `DEFAULT_PATH` for a scene that had none,
+    /// and keeps watching what was written.
+    pub fn save(&mut self) -> io::Result<&Path> {
+        let path = self
+            .path
+            .get_or_insert_with(|| PathBuf::from(LiveScene::DEFAULT_PATH));
+        self.scene.save(&*path)?;
+        self.stamp = stamp(path);
+        Ok(path)
+    }
+}
+
+fn stamp(path: &Path) -> Option<(SystemTime, u64)> {
+    let metadata = fs::metadata(path).ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
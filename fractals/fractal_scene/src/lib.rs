@@ -0,0 +1,840 @@
+//! Scene files for the fractal sketches: which fractal to draw and how, in TOML or JSON.
+//!
+//! ```toml
+//! depth = 4
+//! stroke = 4.0
+//! background = "white"
+//! palette = ["#1e90ff", "#ff8000"]
+//!
+//! [fractal]
+//! kind = "koch"
+//! type = "radial"
+//! sides = 6
+//!
+//! [camera]
+//! center = [100.0, 0.0]
+//! zoom = 8.0
+//! ```
+//!
+//! Every field can be left out and falls back to `Scene::default()`, and a `Scene` turns
+//! into a `Drawing` for the window or for `fractal_export`.
+
+use std::fmt::Write;
+
+use fractal_geometry::apollonian;
+use fractal_geometry::budget::{Budget, Estimate};
+use fractal_geometry::camera::Camera;
+use fractal_geometry::cantor::{CantorRule, Dimension, Mask, Measure};
+use fractal_geometry::circles::{BranchPreset, BranchShape, Branching};
+use fractal_geometry::dimension::{BoxCounting, Theory};
+use fractal_geometry::drawing::{self, Drawing, Rgb, Shape, Style};
+use fractal_geometry::fit::fit_into;
+use fractal_geometry::koch::{
+    koch_in_view, koch_line_with, koch_radial, Bump, KochParams, KochType, RadialLayout,
+};
+use fractal_geometry::lsystem::Preset;
+use fractal_geometry::sierpinski::{self, SierpinskiShape};
+use fractal_geometry::{pt2, Point2};
+use serde::{Deserialize, Serialize};
+
+mod camera;
+mod edit;
+mod file;
+mod text;
+
+pub use edit::Edit;
+pub use file::{Format, LiveScene};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+pub enum Fractal {
+    Koch(Koch),
+    Sierpinski(Sierpinski),
+    Cantor(Cantor),
+    Circles(Circles),
+    Apollonian(Apollonian),
+    Lsystem {
+        #[serde(with = "text")]
+        preset: Preset,
+        #[serde(default)]
+        seed: u64,
+    },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum KochKind {
+    Linear,
+    Radial,
+}
+
+/// A Koch curve with its generator spelled out, see `KochParams`.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Koch {
+    #[serde(rename = "type")]
+    pub koch_type: KochKind,
+    pub sides: u32, // Only for radial curves
+    pub star: u32,  // Skipped vertices for radial star polygons, 1 for a regular polygon
+    pub angle: f32,
+    pub ratios: [f32; 3],
+    #[serde(with = "text")]
+    pub bump: Bump,
+}
+
+impl Koch {
+    pub fn new(koch_type: KochType, star: u32, generator: KochParams) -> Self {
+        let (koch_type, sides) = match koch_type {
+            // Sides only matter once the curve is switched to radial
+            KochType::Linear => (KochKind::Linear, 12),
+            KochType::Radial(sides) => (KochKind::Radial, sides),
+        };
+        Koch {
+            koch_type,
+            sides,
+            star,
+            angle: generator.angle,
+            ratios: generator.ratios,
+            bump: generator.bump,
+        }
+    }
+
+    pub fn koch_type(&self) -> KochType {
+        match self.koch_type {
+            KochKind::Linear => KochType::Linear,
+            KochKind::Radial => KochType::Radial(self.sides),
+        }
+    }
+
+    pub fn generator(&self) -> KochParams {
+        KochParams {
+            angle: self.angle,
+            ratios: self.ratios,
+            bump: self.bump,
+        }
+    }
+}
+
+impl Default for Koch {
+    // The 12-sided snowflake `koch_line` always drew
+    fn default() -> Self {
+        Koch::new(KochType::Radial(12), 1, KochParams::default())
+    }
+}
+
+/// A Cantor set, see `fractal_geometry::cantor::CantorRule`.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Cantor {
+    #[serde(with = "text")]
+    pub mask: Mask,
+    pub widths: Vec<f32>, // Relative widths of the mask's parts, all equal when empty
+    pub fat: bool,        // The Smith-Volterra-Cantor set instead of the mask
+    pub dust: bool,       // Squares for every pair of intervals, instead of rows of bars
+    pub fit: bool,        // Spread the rows over the window instead of 30 units apart
+}
+
+impl Cantor {
+    pub fn rule(&self) -> CantorRule {
+        if self.fat {
+            CantorRule::SmithVolterra
+        } else {
+            CantorRule::Mask {
+                mask: self.mask.clone(),
+                widths: self.widths.clone(),
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum BranchKind {
+    Circle,
+    Square,
+    Polygon,
+}
+
+/// Recursive shapes with their branching rules spelled out, see
+/// `fractal_geometry::circles::Branching`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Circles {
+    pub radius: f32,      // Radius of the largest shape
+    pub angles: Vec<f32>, // One child per angle, in degrees
+    pub center: bool,     // One more child in the middle
+    pub distance: f32,    // From a parent's center to its children's, in parent radii
+    pub ratio: f32,
+    pub rotation: f32, // Degrees every level turns further than its parent
+    pub shape: BranchKind,
+    pub sides: u32, // Only for polygons
+    pub min_radius: f32,
+}
+
+impl Circles {
+    pub fn new(radius: f32, branching: Branching) -> Self {
+        let (shape, sides) = match branching.shape {
+            BranchShape::Circle => (BranchKind::Circle, 6),
+            BranchShape::Square => (BranchKind::Square, 6),
+            BranchShape::Polygon(sides) => (BranchKind::Polygon, sides),
+        };
+        Circles {
+            radius,
+            angles: branching.angles,
+            center: branching.center,
+            distance: branching.distance,
+            ratio: branching.ratio,
+            rotation: branching.rotation,
+            shape,
+            sides,
+            min_radius: branching.min_radius,
+        }
+    }
+
+    pub fn branching(&self) -> Branching {
+        Branching {
+            angles: self.angles.clone(),
+            center: self.center,
+            distance: self.distance,
+            ratio: self.ratio,
+            rotation: self.rotation,
+            shape: match self.shape {
+                BranchKind::Circle => BranchShape::Circle,
+                BranchKind::Square => BranchShape::Square,
+                BranchKind::Polygon => BranchShape::Polygon(self.sides),
+            },
+            min_radius: self.min_radius,
+        }
+    }
+
+    /// The preset these rules are, if any.
+    pub fn preset(&self) -> Option<BranchPreset> {
+        let branching = self.branching();
+        BranchPreset::ALL
+            .into_iter()
+            .find(|preset| preset.branching() == branching)
+    }
+}
+
+impl Default for Circles {
+    // The circles `first-try` always drew
+    fn default() -> Self {
+        Circles::new(500.0, BranchPreset::Four.branching())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SierpinskiKind {
+    Triangle,
+    Carpet,
+    Flake,
+}
+
+/// How the polygons of a Sierpinski fractal are drawn.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Render {
+    #[default]
+    Filled,
+    Outline,  // Every level's outlines, the largest on top
+    Inverted, // The holes, in the color of the level they were cut out of
+}
+
+/// Which palette color each polygon gets.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Coloring {
+    #[default]
+    Shapes, // Polygons take turns, like the shapes of every other fractal
+    Levels, // One color per level of the recursion, starting over after the last
+    // Blended from the first color at the top level to the last at the deepest, or from
+    // the flattest circle of an Apollonian gasket to its most curved
+    Gradient,
+}
+
+/// An Apollonian gasket, see `fractal_geometry::apollonian::Apollonian`.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Apollonian {
+    // Of the three circles the gasket starts from, negative for one around the other two
+    pub curvatures: [f64; 3],
+    pub min_radius: f32, // Smaller circles are left out, in window units
+    pub filled: bool,
+    pub coloring: Coloring, // Levels are generations of circles
+}
+
+impl Apollonian {
+    pub fn gasket(&self) -> apollonian::Apollonian {
+        apollonian::Apollonian::new(self.curvatures)
+    }
+}
+
+impl Default for Apollonian {
+    fn default() -> Self {
+        Apollonian {
+            curvatures: apollonian::Apollonian::default().curvatures,
+            min_radius: 1.0,
+            filled: false,
+            coloring: Coloring::Shapes,
+        }
+    }
+}
+
+/// A member of the Sierpinski family, see `fractal_geometry::sierpinski::Sierpinski`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Sierpinski {
+    pub shape: SierpinskiKind,
+    pub sides: u32, // Only for flakes, 5 for the pentaflake
+    pub equilateral: bool,
+    pub render: Render,
+    pub coloring: Coloring,
+    // Points to draw with the chaos game instead of the polygons at the scene's depth
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub points: Option<u32>,
+}
+
+impl Sierpinski {
+    /// Most points the chaos game may draw, each one is a shape of its own.
+    pub const MAX_POINTS: u32 = 1_000_000;
+
+    pub fn new(shape: SierpinskiShape) -> Self {
+        let (shape, sides) = match shape {
+            SierpinskiShape::Triangle => (SierpinskiKind::Triangle, 5),
+            SierpinskiShape::Carpet => (SierpinskiKind::Carpet, 5),
+            SierpinskiShape::Flake(sides) => (SierpinskiKind::Flake, sides),
+        };
+        Sierpinski {
+            shape,
+            sides,
+            equilateral: false,
+            render: Render::Filled,
+            coloring: Coloring::Shapes,
+            points: None,
+        }
+    }
+
+    pub fn shape(&self) -> SierpinskiShape {
+        match self.shape {
+            SierpinskiKind::Triangle => SierpinskiShape::Triangle,
+            SierpinskiKind::Carpet => SierpinskiShape::Carpet,
+            SierpinskiKind::Flake => SierpinskiShape::Flake(self.sides),
+        }
+    }
+}
+
+impl Default for Sierpinski {
+    // The triangle `sierpinski_triangle` always drew, stretched over the window
+    fn default() -> Self {
+        Sierpinski::new(SierpinskiShape::Triangle)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Scene {
+    pub depth: u32,
+    pub stroke: f32,
+    #[serde(with = "text")]
+    pub background: Rgb,
+    // Shapes take turns using these colors, so one color draws everything in it
+    #[serde(with = "text::list")]
+    pub palette: Vec<Rgb>,
+    pub fractal: Fractal,
+    // Looks at the fractal as it would be drawn into the window without one
+    #[serde(with = "camera")]
+    pub camera: Camera,
+}
+
+impl Default for Scene {
+    fn default() -> Self {
+        Scene {
+            depth: 4,
+            stroke: 4.0,
+            background: drawing::WHITE,
+            palette: vec![drawing::BLACK],
+            fractal: Fractal::Koch(Koch::default()),
+            camera: Camera::default(),
+        }
+    }
+}
+
+impl Scene {
+    /// The default look with another fractal, e.g. a sketch's own starting point.
+    pub fn new(fractal: Fractal, depth: u32, stroke: f32) -> Self {
+        Scene {
+            fractal,
+            depth,
+            stroke,
+            ..Scene::default()
+        }
+    }
+
+    /// The same scene at another depth, e.g. for the frames of `--frames`.
+    pub fn at_depth(&self, depth: u32) -> Scene {
+        Scene {
+            depth,
+            ..self.clone()
+        }
+    }
+
+    /// What drawing the fractal would cost, `None` for the chaos game, which draws the
+    /// points it is asked for.
+    pub fn estimate(&self) -> Option<Estimate> {
+        match &self.fractal {
+            Fractal::Koch(koch) => Some(match koch.koch_type() {
+                KochType::Linear => Estimate::koch_linear(self.depth),
+                KochType::Radial(sides) => {
+                    Estimate::koch_radial(&RadialLayout::new(sides).star(koch.star), self.depth)
+                }
+            }),
+            Fractal::Lsystem { preset, seed } => {
+                Some(Estimate::lsystem(&preset.lsystem().seed(*seed), self.depth))
+            }
+            Fractal::Cantor(cantor) if cantor.dust => {
+                Some(Estimate::cantor_dust(&cantor.rule(), self.depth))
+            }
+            Fractal::Cantor(cantor) => Some(Estimate::cantor(&cantor.rule(), self.depth)),
+            Fractal::Sierpinski(sierpinski) if sierpinski.points.is_none() => {
+                Some(Estimate::sierpinski(sierpinski.shape(), self.depth))
+            }
+            Fractal::Circles(circles) => Some(Estimate::branching(
+                &circles.branching(),
+                circles.radius,
+                self.depth,
+            )),
+            Fractal::Apollonian(_) => Some(Estimate::apollonian(self.depth)),
+            _ => None,
+        }
+    }
+
+    /// The dimension the fractal should have, `None` for L-systems, which have no formula
+    /// for it here. Recursive shapes only reach theirs when the copies do not overlap.
+    pub fn theory(&self) -> Option<Theory> {
+        Some(match &self.fractal {
+            Fractal::Koch(koch) => {
+                // The two ends of the split segment and the two sides of the bump
+                let [start, middle, end] = koch.ratios.map(f64::from);
+                let side = middle / (2.0 * f64::from(koch.angle).to_radians().cos());
+                let total = start + middle + end;
+                Theory::copies(vec![start / total, side / total, side / total, end / total])
+            }
+            Fractal::Sierpinski(sierpinski) => {
+                let shape = sierpinski.shape();
+                Theory::copies(vec![shape.ratio(); shape.copies() as usize])
+            }
+            Fractal::Cantor(cantor) if cantor.fat => Theory::Known {
+                value: 1.0,
+                source: "it keeps a positive length",
+            },
+            Fractal::Cantor(cantor) => {
+                let rows = cantor.rule().rows(1);
+                let line = Theory::copies(rows[1].iter().map(|(start, end)| end - start).collect());
+                match line {
+                    _ if !cantor.dust => line,
+                    // Every pair of intervals makes a square
+                    Theory::Similar(set) => Theory::Similar(Dimension {
+                        copies: set.copies * set.copies,
+                        scale: set.scale,
+                    }),
+                    _ => Theory::Known {
+                        value: 2.0 * line.value(),
+                        source: "twice the set's",
+                    },
+                }
+            }
+            Fractal::Circles(circles) => Theory::copies(vec![
+                f64::from(circles.ratio);
+                circles.branching().children()
+            ]),
+            Fractal::Apollonian(_) => Theory::Known {
+                value: 1.305688,
+                source: "McMullen's computation",
+            },
+            Fractal::Lsystem { .. } => return None,
+        })
+    }
+
+    /// A box-counting estimate of the dimension, over the fractal as it is drawn into
+    /// a `width` x `height` window without the camera. Cantor sets drawn as rows and
+    /// recursive shapes only count their smallest pieces, the set rather than the steps
+    /// towards it.
+    pub fn box_counting(&self, width: f32, height: f32) -> Option<BoxCounting> {
+        let shapes: Vec<Shape> = match &self.fractal {
+            Fractal::Cantor(cantor) if !cantor.dust => cantor
+                .rule()
+                .rows(self.depth)
+                .last()?
+                .iter()
+                .map(|&(start, end)| {
+                    Shape::Line(pt2(start as f32 * width, 0.0), pt2(end as f32 * width, 0.0))
+                })
+                .collect(),
+            Fractal::Circles(circles) => {
+                let branching = circles.branching();
+                let branches = branching.branches(pt2(0.0, 0.0), circles.radius, self.depth);
+                // Parents come right before their children, so leaves are followed by a
+                // branch no deeper than themselves
+                let next_levels = branches.iter().skip(1).map(|branch| branch.level);
+                branches
+                    .iter()
+                    .zip(next_levels.chain([0]))
+                    .filter(|(branch, next)| *next <= branch.level)
+                    .map(|(branch, _)| match branching.shape.outline(branch) {
+                        Some(corners) => Shape::Polygon(corners),
+                        None => Shape::Circle {
+                            center: branch.center,
+                            radius: branch.radius,
+                        },
+                    })
+                    .collect()
+            }
+            _ => {
+                let scene = Scene {
+                    camera: Camera::default(),
+                    ..self.clone()
+                };
+                let drawing = scene.drawing(width, height);
+                drawing.shapes.into_iter().map(|(shape, _)| shape).collect()
+            }
+        };
+        BoxCounting::of(&shapes)
+    }
+
+    /// Everything a scene file can get wrong that its syntax cannot, checked before
+    /// anything is generated.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.palette.is_empty() {
+            return Err("the palette needs at least one color".to_string());
+        }
+        if self.stroke.is_nan() || self.stroke < 0.0 {
+            return Err(format!("stroke must not be negative, got {}", self.stroke));
+        }
+        let zoom = self.camera.scale;
+        if zoom.is_nan() || zoom <= 0.0 || zoom > Camera::MAX_SCALE {
+            return Err(format!(
+                "zoom must be positive and at most {:e}, got {zoom}",
+                Camera::MAX_SCALE
+            ));
+        }
+        // Same limits as the `fractal` binary's `--depth`
+        let max_depth = match self.fractal {
+            Fractal::Sierpinski(_) => 16,
+            _ => 32,
+        };
+        if self.depth > max_depth {
+            return Err(format!(
+                "depth must be at most {max_depth}, got {}",
+                self.depth
+            ));
+        }
+        match &self.fractal {
+            Fractal::Koch(koch) => {
+                koch.generator().validate()?;
+                if let KochType::Radial(sides) = koch.koch_type() {
+                    RadialLayout::new(sides).star(koch.star).validate()?;
+                }
+            }
+            Fractal::Sierpinski(sierpinski) => {
+                sierpinski.shape().validate()?;
+                if let Some(points) = sierpinski.points {
+                    if points > Sierpinski::MAX_POINTS {
+                        return Err(format!(
+                            "the chaos game draws at most {} points, got {points}",
+                            Sierpinski::MAX_POINTS
+                        ));
+                    }
+                }
+            }
+            Fractal::Cantor(cantor) => cantor.rule().validate()?,
+            Fractal::Circles(circles) => {
+                let radius = circles.radius;
+                if radius.is_nan() || radius <= 0.0 {
+                    return Err(format!("radius must be positive, got {radius}"));
+                }
+                circles.branching().validate()?;
+            }
+            Fractal::Apollonian(gasket) => {
+                gasket.gasket().validate()?;
+                if gasket.min_radius.is_nan() || gasket.min_radius <= 0.0 {
+                    return Err(format!(
+                        "the smallest radius must be positive, got {}",
+                        gasket.min_radius
+                    ));
+                }
+            }
+            _ => {}
+        }
+        if let Some(estimate) = self.estimate() {
+            Budget::default()
+                .check(&estimate)
+                .map_err(|err| format!("at depth {} {err}", self.depth))?;
+        }
+        Ok(())
+    }
+
+    pub fn drawing(&self, width: f32, height: f32) -> Drawing {
+        let mut drawing = Drawing::new(width, height, self.background);
+        // Koch curves and Sierpinski triangles are generated for the camera, so they keep
+        // their detail however far it zooms in; everything else is just moved in front of it
+        let camera = (self.camera != Camera::default()).then_some(&self.camera);
+        let mut seen_by_camera = false;
+        let mut colors = self.palette.iter().copied().cycle();
+        let mut color = || colors.next().unwrap_or(drawing::BLACK);
+        // The color of a level or generation, going round the palette
+        let palette_at = |index: u32| {
+            let index = index as usize % self.palette.len().max(1);
+            self.palette.get(index).copied().unwrap_or(drawing::BLACK)
+        };
+        let stroke = |color| Style::stroke(color, self.stroke);
+
+        match &self.fractal {
+            Fractal::Koch(koch) => match koch.koch_type() {
+                KochType::Linear => {
+                    let start = pt2(-width / 2.0, 0.0);
+                    let end = pt2(width / 2.0, 0.0);
+                    let generator = koch.generator();
+                    if let Some(camera) = camera {
+                        let corners = [start, end];
+                        for points in
+                            koch_in_view(&corners, self.depth, &generator, camera, width, height)
+                        {
+                            drawing.push(Shape::Polyline(points), stroke(color()));
+                        }
+                        seen_by_camera = true;
+                    } else {
+                        let points =
+                            koch_line_with(start, end, self.depth, KochType::Linear, &generator);
+                        drawing.push(Shape::Polyline(points), stroke(color()));
+                    }
+                }
+                KochType::Radial(sides) => {
+                    // As large as fits the window, less the stroke
+                    let size = |side: f32| (side - self.stroke).max(0.0);
+                    let generator = koch.generator();
+                    let layout = RadialLayout::new(sides).star(koch.star).fit_into(
+                        pt2(0.0, 0.0),
+                        size(width),
+                        size(height),
+                        self.depth,
+                        &generator,
+                    );
+                    if let Some(camera) = camera {
+                        // One color per loop of a star, like the polylines of `koch_radial`
+                        for corners in layout.corners() {
+                            let style = stroke(color());
+                            for points in koch_in_view(
+                                &corners, self.depth, &generator, camera, width, height,
+                            ) {
+                                drawing.push(Shape::Polyline(points), style);
+                            }
+                        }
+                        seen_by_camera = true;
+                    } else {
+                        for points in koch_radial(&layout, self.depth, &generator) {
+                            drawing.push(Shape::Polyline(points), stroke(color()));
+                        }
+                    }
+                }
+            },
+            Fractal::Sierpinski(sierpinski) => {
+                let fractal = sierpinski::Sierpinski::new(sierpinski.shape())
+                    .equilateral(sierpinski.equilateral)
+                    .fit_into(pt2(0.0, 0.0), width, height);
+                if let Some(points) = sierpinski.points {
+                    // The chaos game has no depth to add when zoomed, the points just move
+                    let shape = Shape::Points {
+                        points: fractal.chaos_game(points, 0),
+                        size: self.stroke.max(1.0),
+                    };
+                    drawing.push(shape, Style::fill(color()));
+                } else {
+                    let pieces = match camera {
+                        Some(camera) => {
+                            seen_by_camera = true;
+                            fractal.pieces_in_view(self.depth, camera, width, height)
+                        }
+                        None => fractal.pieces(self.depth),
+                    };
+                    let mut level_color = |level: u32| match sierpinski.coloring {
+                        Coloring::Shapes => color(),
+                        Coloring::Levels => palette_at(level),
+                        // Zoomed in, the levels past `depth` keep the last color
+                        Coloring::Gradient => drawing::gradient(
+                            &self.palette,
+                            level as f32 / self.depth.max(1) as f32,
+                        ),
+                    };
+                    match sierpinski.render {
+                        Render::Filled => {
+                            for piece in pieces.into_iter().filter(|piece| piece.leaf) {
+                                let style = Style::fill(level_color(piece.level));
+                                drawing.push(Shape::Polygon(piece.points), style);
+                            }
+                        }
+                        Render::Outline => {
+                            for piece in pieces.into_iter().rev() {
+                                let style =
+                                    Style::stroke(level_color(piece.level), self.stroke.max(1.0));
+                                drawing.push(Shape::Polygon(piece.points), style);
+                            }
+                        }
+                        Render::Inverted => {
+                            // Parents before their copies, which cover all but the holes
+                            for piece in pieces {
+                                let fill = if piece.leaf {
+                                    self.background
+                                } else {
+                                    level_color(piece.level)
+                                };
+                                drawing.push(Shape::Polygon(piece.points), Style::fill(fill));
+                            }
+                        }
+                    }
+                }
+            }
+            Fractal::Cantor(cantor) if cantor.dust => {
+                // As large a square as fits the window
+                let side = width.min(height);
+                let corner = pt2(-side / 2.0, -side / 2.0);
+                for (min, max) in cantor.rule().dust(self.depth) {
+                    let min = corner + min.as_f32() * side;
+                    let max = corner + max.as_f32() * side;
+                    let square = vec![min, pt2(max.x, min.y), max, pt2(min.x, max.y)];
+                    drawing.push(Shape::Polygon(square), Style::fill(color()));
+                }
+            }
+            Fractal::Cantor(cantor) => {
+                // One row of bars per split from the bottom of the window up, either 30 units
+                // apart or as far apart as fits, with bars thin enough to stay apart
+                let rows = cantor.rule().rows(self.depth);
+                let mut bottom = -(height / 2.0) + self.stroke;
+                let (spacing, weight) = match (cantor.fit, self.depth) {
+                    (false, _) => (30.0, self.stroke),
+                    (true, 0) => {
+                        bottom = 0.0;
+                        (0.0, self.stroke)
+                    }
+                    (true, depth) => {
+                        let spacing = (height - 2.0 * self.stroke) / depth as f32;
+                        (spacing, self.stroke.min(spacing / 2.0))
+                    }
+                };
+                let x = |t: f64| -(width / 2.0) + t as f32 * width;
+                for (split, row) in rows.iter().enumerate() {
+                    let y = bottom + spacing * split as f32;
+                    for &(start, end) in row {
+                        let bar = Shape::Line(pt2(x(start), y), pt2(x(end), y));
+                        drawing.push(bar, Style::stroke(color(), weight));
+                    }
+                }
+                drawing.overlay = Some(cantor_overlay(&rows));
+            }
+            Fractal::Circles(circles) => {
+                // Children stop at the scene's depth or the smallest radius, whichever is first
+                let branching = circles.branching();
+                let center = pt2(width / 20.0, height / 20.0);
+                for branch in branching.branches(center, circles.radius, self.depth) {
+                    let shape = match branching.shape.outline(&branch) {
+                        Some(corners) => Shape::Polygon(corners),
+                        None => Shape::Circle {
+                            center: branch.center,
+                            radius: branch.radius,
+                        },
+                    };
+                    drawing.push(shape, stroke(color()));
+                }
+            }
+            Fractal::Apollonian(gasket) => {
+                // The enclosing circle as large as fits the window, less the stroke
+                let radius = width.min(height) / 2.0 - self.stroke;
+                let circles =
+                    gasket
+                        .gasket()
+                        .circles(pt2(0.0, 0.0), radius, self.depth, gasket.min_radius);
+                // Curvatures grow about as fast as the circles get smaller, so blend on a
+                // log scale to give every size its share of the gradient
+                let steepest = circles
+                    .iter()
+                    .map(|c| c.curvature.abs().ln())
+                    .fold(0.0, f64::max);
+                for circle in circles {
+                    let color = match gasket.coloring {
+                        Coloring::Shapes => color(),
+                        Coloring::Levels => palette_at(circle.generation),
+                        Coloring::Gradient => drawing::gradient(
+                            &self.palette,
+                            (circle.curvature.abs().ln() / steepest.max(f64::EPSILON)) as f32,
+                        ),
+                    };
+                    let style = if gasket.filled {
+                        Style::fill(color)
+                    } else {
+                        stroke(color)
+                    };
+                    let shape = Shape::Circle {
+                        center: circle.center,
+                        radius: circle.radius,
+                    };
+                    drawing.push(shape, style);
+                }
+            }
+            Fractal::Lsystem { preset, seed } => {
+                // L-systems come out at turtle scale, so stretch them over most of the window
+                let polylines = preset
+                    .lsystem()
+                    .seed(*seed)
+                    .polylines(self.depth, pt2(0.0, 0.0));
+                for points in fit_into(&polylines, pt2(0.0, 0.0), width * 0.9, height * 0.9) {
+                    drawing.push(Shape::Polyline(points), stroke(color()));
+                }
+            }
+        }
+
+        if let Some(camera) = camera.filter(|_| !seen_by_camera) {
+            for (shape, _) in &mut drawing.shapes {
+                place(shape, camera);
+            }
+        }
+        drawing
+    }
+}
+
+fn place(shape: &mut Shape, camera: &Camera) {
+    let screen = |point: &mut Point2| *point = camera.to_screen(point.as_f64());
+    match shape {
+        Shape::Polyline(points) => points.iter_mut().for_each(screen),
+        Shape::Line(start, end) => {
+            screen(start);
+            screen(end);
+        }
+        Shape::Tri(points) => points.iter_mut().for_each(screen),
+        Shape::Polygon(points) => points.iter_mut().for_each(screen),
+        Shape::Circle { center, radius } => {
+            screen(center);
+            *radius *= camera.scale as f32;
+        }
+        Shape::Points { points, .. } => points.iter_mut().for_each(screen),
+    }
+}
+
+// What is left of every row of a Cantor set, and the dimension those rows work out to
+fn cantor_overlay(rows: &[Vec<(f64, f64)>]) -> String {
+    let mut overlay = String::new();
+    for (split, row) in rows.iter().enumerate() {
+        let measure = Measure::of(row);
+        let plural = if measure.segments == 1 { "" } else { "s" };
+        writeln!(
+            overlay,
+            "row {split}: {} segment{plural}, length {:.4}",
+            measure.segments, measure.length
+        )
+        .unwrap();
+    }
+    match Dimension::of(rows) {
+        Some(dimension) => write!(overlay, "dimension {dimension}").unwrap(),
+        None => write!(overlay, "dimension needs a split").unwrap(),
+    }
+    overlay
+}
@@ -0,0 +1,82 @@
+use fractal_geometry::{pt2, Vec2};
+use fractal_scene::{Edit, Fractal, Koch, KochKind, Scene};
+
+#[test]
+fn depth_and_sides_stay_drawable() {
+    let scene = Scene::default();
+    assert_eq!(scene.edited(Edit::Depth(1)).unwrap().depth, 5);
+    assert_eq!(scene.at_depth(0).edited(Edit::Depth(-1)), None);
+    assert_eq!(
+        scene.at_depth(10).edited(Edit::Depth(1)),
+        None,
+        "over budget"
+    );
+
+    let sides = |scene: &Scene| match &scene.fractal {
+        Fractal::Koch(koch) => koch.sides,
+        other => panic!("expected a Koch curve, got {other:?}"),
+    };
+    assert_eq!(sides(&scene.edited(Edit::Sides(-1)).unwrap()), 11);
+
+    let mut triangle = scene.clone();
+    triangle.fractal = Fractal::Koch(Koch {
+        sides: 3,
+        ..Koch::default()
+    });
+    assert_eq!(triangle.edited(Edit::Sides(-1)), None);
+
+    let mut line = scene.clone();
+    line.fractal = Fractal::Koch(Koch {
+        koch_type: KochKind::Linear,
+        ..Koch::default()
+    });
+    assert_eq!(line.edited(Edit::Sides(1)), None);
+}
+
+#[test]
+fn next_fractal_cycles_through_every_kind() {
+    let mut scene = Scene::default().at_depth(10);
+    let mut names = Vec::new();
+    for _ in 0..11 {
+        scene = scene.edited(Edit::NextFractal).unwrap();
+        assert!(scene.validate().is_ok());
+        names.push(scene.fractal.to_string());
+    }
+    assert_eq!(
+        names,
+        [
+            "sierpinski",
+            "sierpinski carpet",
+            "5-flake",
+            "cantor",
+            "circles, radius 500",
+            "circles two, radius 500",
+            "circles apollonian, radius 500",
+            "circles vicsek, radius 500",
+            "apollonian -1, 2, 2",
+            "lsystem dragon",
+            "koch, 12 sides"
+        ]
+    );
+    assert!(
+        scene.depth < 10,
+        "depth 10 is over budget for the snowflake"
+    );
+}
+
+#[test]
+fn zoom_keeps_the_point_under_the_cursor() {
+    let scene = Scene::default();
+    let about = pt2(100.0, 50.0);
+    let zoomed = scene
+        .edited(Edit::Zoom { factor: 2.0, about })
+        .unwrap()
+        .edited(Edit::Pan(Vec2::new(5.0, 0.0)))
+        .unwrap();
+    assert_eq!(zoomed.camera.scale, 2.0);
+    // The point under the cursor stays put, then moves with the drag
+    let world = scene.camera.to_world(about);
+    let moved = zoomed.camera.to_screen(world);
+    assert!(moved.distance(about + pt2(5.0, 0.0)) < 1e-3, "{moved}");
+    assert_eq!(scene.edited(Edit::Zoom { factor: 0.0, about }), None);
+}
@@ -0,0 +1,267 @@
+use std::fs;
+use std::path::PathBuf;
+
+use fractal_geometry::drawing::{Rgb, Shape, BLACK, WHITE};
+use fractal_geometry::koch::{KochParams, KochType};
+use fractal_geometry::lsystem::Preset;
+use fractal_geometry::pt2;
+use fractal_scene::{Circles, Coloring, Format, Fractal, Koch, LiveScene, Render, Scene};
+
+fn temp_file(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("fractal_scene_{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    dir.join(name)
+}
+
+#[test]
+fn missing_fields_fall_back_to_defaults() {
+    let scene = Scene::parse(
+        "depth = 2\n[fractal]\nkind = \"koch\"\nsides = 6\n",
+        Format::Toml,
+    );
+    let scene = scene.unwrap();
+    assert_eq!(scene.depth, 2);
+    assert_eq!(scene.palette, Scene::default().palette);
+    match scene.fractal {
+        Fractal::Koch(koch) => {
+            assert_eq!(koch.koch_type(), KochType::Radial(6));
+            assert_eq!(koch.generator(), KochParams::default());
+        }
+        other => panic!("expected a Koch curve, got {other:?}"),
+    }
+
+    let scene = Scene::parse(
+        r#"{"fractal": {"kind": "lsystem", "preset": "dragon"}}"#,
+        Format::Json,
+    );
+    assert_eq!(
+        scene.unwrap().fractal,
+        Fractal::Lsystem {
+            preset: Preset::Dragon,
+            seed: 0
+        }
+    );
+}
+
+#[test]
+fn scenes_survive_a_round_trip_in_both_formats() {
+    let mut scene = Scene::new(
+        Fractal::Koch(Koch::new(KochType::Radial(5), 2, KochParams::cesaro(80.0))),
+        3,
+        1.5,
+    );
+    scene.palette = vec![Rgb::new(30, 144, 255), Rgb::new(255, 128, 0)];
+    scene.camera.rotation = 45.0;
+    scene.camera.center = glam::DVec2::new(0.1, -2.5);
+
+    for format in [Format::Toml, Format::Json] {
+        let text = scene.to_text(format);
+        assert_eq!(Scene::parse(&text, format), Ok(scene.clone()), "{text}");
+    }
+    assert!(scene.to_text(Format::Toml).contains("\"#1e90ff\""));
+    assert!(scene.to_text(Format::Json).contains("\"bump\": \"inward\""));
+}
+
+#[test]
+fn bad_scenes_are_errors() {
+    let error = |text: &str| Scene::parse(text, Format::Toml).unwrap_err();
+    assert!(error("depht = 3").contains("depht"));
+    assert!(error("palette = [\"teal\"]").contains("teal"));
+    assert!(error("palette = []").contains("palette"));
+    assert!(error("[fractal]\nkind = \"spiral\"").contains("spiral"));
+    assert!(error("[fractal]\nkind = \"koch\"\nsides = 4\nstar = 2").contains("star polygon"));
+    assert!(error("depth = 20\n[fractal]\nkind = \"koch\"").contains("over the limit"));
+    assert!(error("[fractal]\nkind = \"koch\"\nbump = \"sideways\"").contains("sideways"));
+}
+
+#[test]
+fn the_camera_moves_every_shape() {
+    let mut scene = Scene::new(
+        Fractal::Circles(Circles {
+            radius: 100.0,
+            ..Circles::default()
+        }),
+        0,
+        2.0,
+    );
+    let plain = scene.drawing(400.0, 400.0);
+    // Doubled in size around the middle, then moved by (10, -20)
+    scene.camera.center = glam::DVec2::new(-5.0, 10.0);
+    scene.camera.scale = 2.0;
+    let moved = scene.drawing(400.0, 400.0);
+
+    let circle = |shape: &Shape| match *shape {
+        Shape::Circle { center, radius } => (center, radius),
+        _ => panic!("expected a circle"),
+    };
+    let (center, radius) = circle(&plain.shapes[0].0);
+    let (moved_center, moved_radius) = circle(&moved.shapes[0].0);
+    assert_eq!(moved_center, center * 2.0 + pt2(10.0, -20.0));
+    assert_eq!(moved_radius, radius * 2.0);
+}
+
+#[test]
+fn sierpinski_holes_and_outlines_take_the_color_of_their_level() {
+    let inverted = Scene::parse(
+        "depth = 2\npalette = [\"#ff0000\", \"#00ff00\"]\n\n[fractal]\nkind = \"sierpinski\"\n\
+         shape = \"carpet\"\nrender = \"inverted\"\ncoloring = \"levels\"\n",
+        Format::Toml,
+    )
+    .unwrap();
+    let fills: Vec<Rgb> = inverted
+        .drawing(300.0, 300.0)
+        .shapes
+        .iter()
+        .map(|(_, style)| style.fill.unwrap())
+        .collect();
+    let count = |color: Rgb| fills.iter().filter(|fill| **fill == color).count();
+    // The first square, the 8 it splits into and their 64 copies over the holes
+    assert_eq!(fills[0], Rgb::new(255, 0, 0));
+    assert_eq!(count(Rgb::new(0, 255, 0)), 8);
+    assert_eq!(count(inverted.background), 64);
+
+    let mut outline = inverted.clone();
+    outline.palette = vec![BLACK, WHITE];
+    if let Fractal::Sierpinski(sierpinski) = &mut outline.fractal {
+        sierpinski.render = Render::Outline;
+        sierpinski.coloring = Coloring::Gradient;
+    }
+    let strokes: Vec<Rgb> = outline
+        .drawing(300.0, 300.0)
+        .shapes
+        .iter()
+        .map(|(_, style)| style.stroke.unwrap())
+        .collect();
+    // Deepest first, so the larger outlines end up on top
+    assert_eq!(strokes.first(), Some(&WHITE));
+    assert!(strokes.contains(&Rgb::new(128, 128, 128)));
+    assert_eq!(strokes.last(), Some(&BLACK));
+
+    // Scenes built in code skip `validate`, an empty palette draws black
+    let mut bare = inverted;
+    bare.palette.clear();
+    let (_, style) = bare.drawing(300.0, 300.0).shapes[0];
+    assert_eq!(style.fill, Some(BLACK));
+}
+
+#[test]
+fn cantor_rows_follow_the_mask_and_depth() {
+    let scene = Scene::parse(
+        "depth = 2\n[fractal]\nkind = \"cantor\"\nmask = \"10101\"\n",
+        Format::Toml,
+    )
+    .unwrap();
+    let bars = scene.drawing(500.0, 300.0).shapes;
+    assert_eq!(bars.len(), 1 + 3 + 9);
+    assert_eq!(
+        bars[1].0,
+        Shape::Line(pt2(-250.0, -116.0), pt2(-150.0, -116.0))
+    );
+
+    let mut dust = scene.clone();
+    if let Fractal::Cantor(cantor) = &mut dust.fractal {
+        cantor.dust = true;
+    }
+    assert_eq!(dust.drawing(500.0, 300.0).shapes.len(), 9 * 9);
+    assert!(Scene::parse("[fractal]\nkind = \"cantor\"\nmask = \"0\"", Format::Toml).is_err());
+}
+
+#[test]
+fn fitted_cantor_rows_span_the_window_and_list_their_measure() {
+    let scene = Scene::parse(
+        "depth = 3\nstroke = 10\n[fractal]\nkind = \"cantor\"\nfit = true\n",
+        Format::Toml,
+    )
+    .unwrap();
+    let drawing = scene.drawing(400.0, 200.0);
+    let (Shape::Line(first, _), Shape::Line(last, _)) =
+        (&drawing.shapes[0].0, &drawing.shapes.last().unwrap().0)
+    else {
+        panic!("cantor rows are lines");
+    };
+    assert_eq!((first.y, last.y), (-90.0, 90.0));
+
+    let overlay = drawing.overlay.unwrap();
+    assert!(
+        overlay.starts_with("row 0: 1 segment, length 1.0000\n"),
+        "{overlay}"
+    );
+    assert!(
+        overlay.contains("row 3: 8 segments, length 0.2963"),
+        "{overlay}"
+    );
+    assert!(
+        overlay.ends_with("dimension log 2 / log 3 = 0.6309"),
+        "{overlay}"
+    );
+}
+
+#[test]
+fn box_counting_agrees_with_the_theory_of_every_generator() {
+    // Cantor dust, Vicsek squares and the gasket are left out, their boxes keep landing
+    // on the ends of gaps and they read 0.05 to 0.13 too high at any depth worth drawing
+    let scenes = [
+        "depth = 6\n[fractal]\nkind = \"koch\"\ntype = \"linear\"\n",
+        "depth = 8\n[fractal]\nkind = \"sierpinski\"\n",
+        "[fractal]\nkind = \"sierpinski\"\npoints = 200000\n",
+        "depth = 8\n[fractal]\nkind = \"cantor\"\n",
+        "depth = 8\n[fractal]\nkind = \"circles\"\nangles = [0, 180]\nmin_radius = 1\n",
+    ];
+    for toml in scenes {
+        let scene = Scene::parse(toml, Format::Toml).unwrap();
+        let estimate = scene.box_counting(800.0, 800.0).unwrap();
+        let theory = scene.theory().unwrap();
+        assert!(
+            (estimate.dimension() - theory.value()).abs() < 0.05,
+            "{}: {estimate}\nexpected {theory}",
+            scene.fractal
+        );
+    }
+}
+
+#[test]
+fn live_scenes_reload_changed_files_and_keep_the_last_good_one() {
+    let path = temp_file("live.toml");
+    fs::write(&path, "depth = 1\n").unwrap();
+    let mut live = LiveScene::watch(&path).unwrap();
+    assert_eq!(live.scene().depth, 1);
+    assert!(!live.reload().unwrap(), "nothing changed yet");
+
+    fs::write(&path, "depth = 2 # deeper\n").unwrap();
+    assert!(live.reload().unwrap());
+    assert_eq!(live.scene().depth, 2);
+
+    fs::write(&path, "depth = \"three\"\n").unwrap();
+    assert!(live.reload().is_err());
+    assert_eq!(live.scene().depth, 2);
+    fs::write(&path, "depth = 2 # fixed\n").unwrap();
+    assert!(
+        live.reload().unwrap(),
+        "a fixed file counts even when the scene is the same"
+    );
+
+    assert_eq!(live.save().unwrap(), path.as_path());
+    assert_eq!(Scene::load(&path).unwrap().fractal, live.scene().fractal);
+    assert!(!live.reload().unwrap(), "saving is not a change to reload");
+
+    let json = temp_file("saved.json");
+    Scene::default().save(&json).unwrap();
+    let text = fs::read_to_string(&json).unwrap();
+    assert!(text.trim_start().starts_with('{'));
+    assert_eq!(Scene::load(&json).unwrap(), Scene::default());
+}
+
+#[test]
+fn gasket_generations_fall_back_to_black_without_a_palette() {
+    let mut scene = Scene::parse(
+        "depth = 2\n[fractal]\nkind = \"apollonian\"\ncoloring = \"levels\"\n",
+        Format::Toml,
+    )
+    .unwrap();
+    scene.palette.clear();
+    let drawing = scene.drawing(400.0, 400.0);
+    assert!(drawing
+        .shapes
+        .iter()
+        .all(|(_, style)| style.stroke == Some(BLACK)));
+}
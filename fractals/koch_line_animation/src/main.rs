@@ -1,7 +1,37 @@
+use fractal_geometry::drawing::{self, Drawing, Shape, Style};
+use fractal_geometry::koch::{koch_line_into, KochParams, KochType, RadialLayout};
+use fractal_nannou::{draw_polyline, SketchConfig};
 use nannou::prelude::*;
 
+const DEPTH: u32 = 6; // Total depth you want to reach
+const KOCH_TYPE: KochType = KochType::Radial(8); // Change as needed
+
 fn main() {
-    nannou::app(model).update(update).view(view).run();
+    // `--svg out.svg` and `--png out.png` write the fully grown curve to a file instead of
+    // animating it, at the window's size unless `--size <w>x<h>` says otherwise
+    let config = SketchConfig::from_args((800, 600), ()).unwrap_or_else(|err| {
+        eprintln!("error: {err}");
+        std::process::exit(1);
+    });
+    match config.export_with(grown) {
+        Ok(true) => return,
+        Ok(false) => {}
+        Err(err) => {
+            eprintln!("error: failed to export: {err}");
+            std::process::exit(1);
+        }
+    }
+
+    config.app(model).update(update).view(view).run();
+}
+
+// The curve at its full depth
+fn grown(width: f32, height: f32) -> Drawing {
+    let mut drawing = Drawing::new(width, height, drawing::WHITE);
+    let mut points = Vec::new();
+    curve(&mut points, pt2(0.0, 0.0), width, height, DEPTH, KOCH_TYPE);
+    drawing.push(Shape::Polyline(points), Style::stroke(drawing::BLACK, 2.0));
+    drawing
 }
 
 struct Model {
@@ -13,13 +43,19 @@ struct Model {
     points: Vec<Point2>,
 }
 
-fn model(app: &App) -> Model {
+fn model(app: &App, config: SketchConfig) -> Model {
     app.set_loop_mode(LoopMode::rate_fps(10.0)); // Control frame rate of animation
 
     let draw = app.draw();
     draw.background().color(WHITE);
 
-    let _window = app.new_window().size(800, 600).view(view).build().unwrap();
+    let (width, height) = config.window;
+    let _window = app
+        .new_window()
+        .size(width, height)
+        .view(view)
+        .build()
+        .unwrap();
 
     let boundary = app.window_rect();
     let middle = boundary.xy();
@@ -30,9 +66,9 @@ fn model(app: &App) -> Model {
 
     Model {
         current_depth: 0, // Start with 0 depth
-        depth: 6,         // Total depth you want to reach
+        depth: DEPTH,
         draw,
-        koch_type: KochType::Radial(8),         // Change as needed
+        koch_type: KOCH_TYPE,
         last_update: std::time::Instant::now(), // Control animation speed
         points: vec![start, end],               // Initial points
     }
@@ -52,69 +88,41 @@ fn update(app: &App, model: &mut Model, _update: Update) {
             // Calculate the new points based on the current depth
             // Update model.points with new points to draw
             let boundary = app.window_rect();
-            let middle = boundary.xy();
-            let start = pt2(middle.x - boundary.w() / 2.0, middle.y);
-            let end = pt2(middle.x + boundary.w() / 2.0, middle.y);
-            model.points = koch_line(start, end, model.current_depth, model.koch_type.clone());
+            curve(
+                &mut model.points,
+                boundary.xy(),
+                boundary.w(),
+                boundary.h(),
+                model.current_depth,
+                model.koch_type,
+            );
         }
     }
 }
 
-#[derive(Clone)]
-enum KochType {
-    Linear,
-    Radial(u32), // Number of sides
-}
-
-fn koch_line(start: Point2, end: Point2, depth: u32, koch_type: KochType) -> Vec<Point2> {
-    let mut points = Vec::new();
-
-    match koch_type {
-        KochType::Linear => {
-            // Base case of recursion
-            if depth == 0 {
-                points.push(start);
-                points.push(end);
-                return points;
-            } else {
-                // Calculate points for the Koch curve
-                let one_third = start + (end - start) / 3.0;
-                let two_thirds = start + (end - start) * 2.0 / 3.0;
-                let middle_vec = (end - start) / 3.0;
-                let angle = PI / 3.0; // 60 degrees in radians
-                let rotation_matrix = |v: Vec2| -> Vec2 { v.rotate(angle) };
-                let apex = one_third + rotation_matrix(middle_vec);
-
-                // Recursively call koch_line for each segment and append the results
-                points.extend(koch_line(start, one_third, depth - 1, KochType::Linear));
-                points.extend(koch_line(one_third, apex, depth - 1, KochType::Linear));
-                points.extend(koch_line(apex, two_thirds, depth - 1, KochType::Linear));
-                points.extend(koch_line(two_thirds, end, depth - 1, KochType::Linear));
-            }
-        }
+// Overwrites `points` with the curve at `depth`: a straight one across the window, or a
+// radial one fitted into it. The buffer is reused, so growing the curve does not allocate
+// a new one every step.
+fn curve(
+    points: &mut Vec<Point2>,
+    middle: Point2,
+    width: f32,
+    height: f32,
+    depth: u32,
+    koch_type: KochType,
+) {
+    let params = KochParams::default();
+    let radius = match koch_type {
+        KochType::Linear => width / 2.0,
         KochType::Radial(sides) => {
-            let center = pt2(0.0, 0.0); // Center of the circle
-            let radius = 300.0; // Radius of the radial Koch curve
-
-            for i in 0..sides {
-                let angle_start = i as f32 * TAU / sides as f32;
-                let angle_end = (i as f32 + 1.0) * TAU / sides as f32;
-
-                let start = pt2(
-                    center.x + angle_start.cos() * radius,
-                    center.y + angle_start.sin() * radius,
-                );
-
-                let end = pt2(
-                    center.x + angle_end.cos() * radius,
-                    center.y + angle_end.sin() * radius,
-                );
-
-                points.extend(koch_line(start, end, depth, KochType::Linear)); // Use Linear here to avoid infinite recursion
-            }
+            let layout = RadialLayout::new(sides).fit_into(middle, width, height, depth, &params);
+            layout.radius
         }
-    }
-    points
+    };
+    // A radial curve goes around the circle with `start` and `end` as its diameter
+    let start = pt2(middle.x - radius, middle.y);
+    let end = pt2(middle.x + radius, middle.y);
+    koch_line_into(points, start, end, depth, koch_type, &params);
 }
 
 fn view(app: &App, model: &Model, frame: Frame) {
@@ -124,14 +132,7 @@ fn view(app: &App, model: &Model, frame: Frame) {
     draw.background().color(WHITE);
 
     // Use the current points in model.points to draw the curve as it currently is
-    // You might draw lines between each consecutive pair of points
-    for i in 0..model.points.len() - 1 {
-        draw.line()
-            .start(model.points[i])
-            .end(model.points[i + 1])
-            .stroke_weight(2.0)
-            .color(BLACK);
-    }
+    draw_polyline(&draw, &model.points, 2.0, BLACK);
 
     // Finish and present the frame
     draw.to_frame(app, &frame).unwrap();
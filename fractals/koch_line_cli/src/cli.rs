@@ -0,0 +1,469 @@
+//! Command line parsing and validation, kept apart from nannou so it can be tested
+//! without opening a window.
+
+use std::ffi::OsString;
+use std::path::PathBuf;
+
+use clap::builder::PossibleValue;
+use clap::error::ErrorKind;
+use clap::{Arg, ArgAction, ArgMatches, Command, ValueEnum};
+use fractal_export::{parse_size, Export};
+use fractal_geometry::budget::{Budget, Estimate};
+use fractal_geometry::koch::{Bump, KochParams, KochType, RadialLayout};
+use fractal_geometry::lsystem::Preset;
+use fractal_geometry::{pt2, Point2};
+
+const EXAMPLES: &str = "\
+Examples:
+  koch_line_cli --type radial --sides 6 --depth 4
+  koch_line_cli --type radial --sides 5 --star 2 --center -150,0 --depth 3
+  koch_line_cli --variant cesaro --angle 85 --depth 5 --png cesaro.png --size 2048x2048
+  koch_line_cli --type lsystem --lsystem dragon --depth 12
+  koch_line_cli --type radial --sides 50 --depth 15 --dry-run";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CurveType {
+    Linear,
+    Radial,
+    Lsystem,
+}
+
+impl ValueEnum for CurveType {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[CurveType::Linear, CurveType::Radial, CurveType::Lsystem]
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        Some(match self {
+            CurveType::Linear => PossibleValue::new("linear").help("One curve across the window"),
+            CurveType::Radial => {
+                PossibleValue::new("radial").help("Curves around a polygon or star polygon")
+            }
+            CurveType::Lsystem => {
+                PossibleValue::new("lsystem").help("One of the --lsystem presets")
+            }
+        })
+    }
+}
+
+/// Everything needed to draw the curve.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Curve {
+    pub koch_type: KochType,
+    pub lsystem: Option<Preset>,
+    pub seed: u64,
+    pub generator: KochParams,
+    pub layout: RadialLayout,
+    pub fit: bool, // Size the radial layout to the window instead of using its radius
+    pub depth: u32,
+}
+
+impl Curve {
+    // What drawing the curve at `depth` would cost
+    pub fn estimate(&self, depth: u32) -> Estimate {
+        match (self.lsystem, self.koch_type) {
+            (Some(preset), _) => Estimate::lsystem(&preset.lsystem().seed(self.seed), depth),
+            (None, KochType::Linear) => Estimate::koch_linear(depth),
+            (None, KochType::Radial(_)) => Estimate::koch_radial(&self.layout, depth),
+        }
+    }
+}
+
+/// A validated command line.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Options {
+    pub curve: Curve,
+    pub export: Export,
+    pub budget: Budget,
+    pub dry_run: bool,
+    pub warning: Option<String>, // Set when --cap lowered the depth
+}
+
+pub fn command() -> Command {
+    Command::new("Koch Curve Generator")
+        .version("1.0")
+        .about("Draws a Koch Curve")
+        .after_help(EXAMPLES)
+        .arg(
+            Arg::new("type")
+                .short('t')
+                .long("type")
+                .value_parser(clap::value_parser!(CurveType))
+                .default_value("linear")
+                .help("Type of Koch curve"),
+        )
+        .arg(
+            Arg::new("lsystem")
+                .short('l')
+                .long("lsystem")
+                .value_name("NAME")
+                .value_parser(|name: &str| name.parse::<Preset>())
+                .help(
+                    "L-system drawn by `--type lsystem`: koch, snowflake, quadratic-koch, \
+                     dragon, hilbert, gosper, plant, bush, coastline or context-plant \
+                     (defaults to koch)",
+                ),
+        )
+        .arg(
+            Arg::new("seed")
+                .long("seed")
+                .value_parser(clap::value_parser!(u64))
+                .default_value("0")
+                .help("Seed for L-systems with random rules, the same seed draws the same shape"),
+        )
+        .arg(
+            Arg::new("sides")
+                .short('s')
+                .long("sides")
+                .value_parser(clap::value_parser!(u32))
+                .default_value("5")
+                .help("Number of sides for radial, at least 3 (ignored if type is linear)"),
+        )
+        .arg(
+            Arg::new("depth")
+                .short('d')
+                .long("depth")
+                // Deeper than 32 the segment count no longer fits in 64 bits
+                .value_parser(clap::value_parser!(u32).range(..=32))
+                .default_value("4")
+                .help("Depth of recursion for the Koch curve"),
+        )
+        .arg(
+            Arg::new("center")
+                .long("center")
+                .value_name("X,Y")
+                .value_parser(parse_point)
+                .help("Center of a radial curve, relative to the middle of the window"),
+        )
+        .arg(
+            Arg::new("radius")
+                .long("radius")
+                .value_parser(clap::value_parser!(f32))
+                .help("Radius of a radial curve (defaults to as large as fits the window)"),
+        )
+        .arg(
+            Arg::new("rotation")
+                .long("rotation")
+                .value_parser(clap::value_parser!(f32))
+                .default_value("0")
+                .help("Rotation of a radial curve in degrees, counter-clockwise"),
+        )
+        .arg(
+            Arg::new("star")
+                .long("star")
+                .value_name("K")
+                .value_parser(clap::value_parser!(u32).range(1..))
+                .default_value("1")
+                .help("Connect every K-th vertex of a radial curve, e.g. `--sides 5 --star 2`"),
+        )
+        .arg(
+            Arg::new("side-depths")
+                .long("side-depths")
+                .value_name("D,D,...")
+                .value_delimiter(',')
+                .value_parser(clap::value_parser!(u32).range(..=32))
+                .help("Depth of each side of a radial curve, repeated around it, e.g. 4,2"),
+        )
+        .arg(
+            Arg::new("variant")
+                .long("variant")
+                .value_parser(["koch", "cesaro", "anti-snowflake"])
+                .default_value("koch")
+                .help("Starting generator, tweaked further by --angle, --ratios and --bump"),
+        )
+        .arg(
+            Arg::new("angle")
+                .short('a')
+                .long("angle")
+                .value_parser(clap::value_parser!(f32))
+                .help("Base angle of each bump in degrees, between 0 and 90 (defaults to 60)"),
+        )
+        .arg(
+            Arg::new("ratios")
+                .short('r')
+                .long("ratios")
+                .value_name("A/B/C")
+                .value_parser(parse_ratios)
+                .help("How each segment is split around its bump, e.g. 0.3/0.4/0.3"),
+        )
+        .arg(
+            Arg::new("bump")
+                .short('b')
+                .long("bump")
+                .value_parser(|name: &str| name.parse::<Bump>())
+                .help("Side the bumps go on: outward, inward or alternating (by depth)"),
+        )
+        .arg(
+            Arg::new("svg")
+                .long("svg")
+                .value_name("FILE")
+                .value_parser(clap::value_parser!(PathBuf))
+                .help("Write the curve to an SVG file instead of opening a window"),
+        )
+        .arg(
+            Arg::new("png")
+                .long("png")
+                .value_name("FILE")
+                .value_parser(clap::value_parser!(PathBuf))
+                .help("Write the curve to a PNG file instead of opening a window"),
+        )
+        .arg(
+            Arg::new("size")
+                .long("size")
+                .value_name("WxH")
+                .value_parser(parse_size)
+                .help("Size of the SVG or PNG output, e.g. 2048x2048 (defaults to 800x600)"),
+        )
+        .arg(
+            Arg::new("max-segments")
+                .long("max-segments")
+                .value_name("N")
+                .value_parser(clap::value_parser!(u64))
+                .help(format!(
+                    "Refuse curves with more segments than this (defaults to {})",
+                    Budget::DEFAULT_MAX_SEGMENTS
+                )),
+        )
+        .arg(
+            Arg::new("cap")
+                .long("cap")
+                .action(ArgAction::SetTrue)
+                .help("Lower the depth until the curve fits --max-segments instead of refusing"),
+        )
+        .arg(
+            Arg::new("force")
+                .long("force")
+                .action(ArgAction::SetTrue)
+                .help("Draw the curve however large it is"),
+        )
+        .arg(
+            Arg::new("dry-run")
+                .long("dry-run")
+                .action(ArgAction::SetTrue)
+                .help("Print the estimated segment count, vertex count and memory, then exit"),
+        )
+}
+
+/// Parses and validates a command line, program name first.
+pub fn parse_from<I, T>(args: I) -> Result<Options, clap::Error>
+where
+    I: IntoIterator<Item = T>,
+    T: Into<OsString> + Clone,
+{
+    let mut command = command();
+    let matches = command.try_get_matches_from_mut(args)?;
+    options(&matches).map_err(|message| command.error(ErrorKind::ValueValidation, message))
+}
+
+// Everything clap cannot check on its own
+fn options(matches: &ArgMatches) -> Result<Options, String> {
+    // Every argument with a default is always present
+    let value = |id: &str| *matches.get_one::<u32>(id).unwrap();
+
+    let curve_type = *matches.get_one::<CurveType>("type").unwrap();
+    let koch_type = match curve_type {
+        // Only checked here, so other types can leave `--sides` at anything
+        CurveType::Radial if value("sides") < 3 => {
+            return Err(format!(
+                "a radial curve needs at least 3 sides, got {}",
+                value("sides")
+            ))
+        }
+        CurveType::Radial => KochType::Radial(value("sides")),
+        _ => KochType::Linear,
+    };
+    if curve_type != CurveType::Lsystem && matches.contains_id("lsystem") {
+        return Err("--lsystem only applies to --type lsystem".to_string());
+    }
+    let lsystem = match curve_type {
+        CurveType::Lsystem => Some(
+            matches
+                .get_one::<Preset>("lsystem")
+                .copied()
+                .unwrap_or(Preset::Koch),
+        ),
+        _ => None,
+    };
+
+    let angle = matches.get_one::<f32>("angle").copied();
+    let variant = matches.get_one::<String>("variant").unwrap();
+    let mut generator = match variant.as_str() {
+        // Cesàro needs the angle up front to keep its four segments the same length
+        "cesaro" => KochParams::cesaro(angle.unwrap_or(85.0)),
+        "anti-snowflake" => KochParams::anti_snowflake(),
+        _ => KochParams::default(),
+    };
+    if let Some(angle) = angle {
+        generator.angle = angle;
+    }
+    if let Some(ratios) = matches.get_one::<[f32; 3]>("ratios") {
+        generator.ratios = *ratios;
+    }
+    if let Some(bump) = matches.get_one::<Bump>("bump") {
+        generator.bump = *bump;
+    }
+    generator.validate()?;
+
+    let radius = matches.get_one::<f32>("radius").copied();
+    let layout = RadialLayout::new(value("sides"))
+        .center(matches.get_one("center").copied().unwrap_or(pt2(0.0, 0.0)))
+        .radius(radius.unwrap_or(0.0))
+        .rotation(*matches.get_one("rotation").unwrap())
+        .star(value("star"))
+        .depths(
+            matches
+                .get_many::<u32>("side-depths")
+                .map(|depths| depths.copied().collect())
+                .unwrap_or_default(),
+        );
+    if curve_type == CurveType::Radial {
+        layout.validate()?;
+    }
+
+    let mut curve = Curve {
+        koch_type,
+        lsystem,
+        seed: *matches.get_one("seed").unwrap(),
+        generator,
+        layout,
+        fit: radius.is_none(),
+        depth: value("depth"),
+    };
+
+    // Work out what the curve would cost before generating any of it
+    let budget = matches
+        .get_one::<u64>("max-segments")
+        .map_or_else(Budget::default, |&max| Budget::new(max));
+    let dry_run = matches.get_flag("dry-run");
+    let mut warning = None;
+    if !dry_run && !matches.get_flag("force") {
+        let depth = curve.depth;
+        if let Err(err) = budget.check(&curve.estimate(depth)) {
+            match budget.cap(depth, |depth| curve.estimate(depth)) {
+                Some(capped) if matches.get_flag("cap") => {
+                    warning = Some(format!(
+                        "at depth {depth} {err}, drawing depth {capped} instead"
+                    ));
+                    curve.depth = capped;
+                }
+                _ => {
+                    return Err(format!(
+                        "at depth {depth} {err}\n\n\
+                         Lower --depth or raise --max-segments, pass --cap to draw the deepest \
+                         curve that fits, or --force to draw it anyway"
+                    ))
+                }
+            }
+        }
+    }
+
+    Ok(Options {
+        curve,
+        export: Export {
+            svg: matches.get_one::<PathBuf>("svg").cloned(),
+            png: matches.get_one::<PathBuf>("png").cloned(),
+            size: matches.get_one::<(u32, u32)>("size").copied(),
+        },
+        budget,
+        dry_run,
+        warning,
+    })
+}
+
+// Parses split ratios written as `a/b/c`, e.g. `0.3/0.4/0.3`
+fn parse_ratios(ratios: &str) -> Result<[f32; 3], String> {
+    let invalid = || format!("invalid ratios `{ratios}`, expected three numbers like 0.3/0.4/0.3");
+    let parts: Vec<f32> = ratios
+        .split('/')
+        .map(|part| part.trim().parse().map_err(|_| invalid()))
+        .collect::<Result<_, _>>()?;
+    parts.try_into().map_err(|_| invalid())
+}
+
+// Parses a point written as `x,y`, e.g. `-150,40`
+fn parse_point(point: &str) -> Result<Point2, String> {
+    let invalid = || format!("invalid point `{point}`, expected two numbers like -150,40");
+    let (x, y) = point.split_once(',').ok_or_else(invalid)?;
+    let x: f32 = x.trim().parse().map_err(|_| invalid())?;
+    let y: f32 = y.trim().parse().map_err(|_| invalid())?;
+    Ok(pt2(x, y))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &str) -> Result<Options, clap::Error> {
+        parse_from(std::iter::once("koch_line_cli").chain(args.split_whitespace()))
+    }
+
+    fn error(args: &str) -> ErrorKind {
+        parse(args).unwrap_err().kind()
+    }
+
+    #[test]
+    fn defaults_to_a_linear_curve() {
+        let options = parse("").unwrap();
+        assert_eq!(options.curve.koch_type, KochType::Linear);
+        assert_eq!(options.curve.depth, 4);
+        assert!(!options.export.is_requested());
+    }
+
+    #[test]
+    fn typed_values_are_parsed() {
+        let options = parse("-t radial -s 6 -d 3 --star 1 --center 10,-20 --png out.png").unwrap();
+        assert_eq!(options.curve.koch_type, KochType::Radial(6));
+        assert_eq!(options.curve.layout.center, pt2(10.0, -20.0));
+        assert_eq!(options.export.png, Some(PathBuf::from("out.png")));
+
+        let options = parse("--type lsystem --lsystem dragon --ratios 1/2/1").unwrap();
+        assert_eq!(options.curve.lsystem, Some(Preset::Dragon));
+        assert_eq!(options.curve.generator.ratios, [1.0, 2.0, 1.0]);
+    }
+
+    #[test]
+    fn bad_values_are_errors_instead_of_defaults() {
+        assert_eq!(error("--type spiral"), ErrorKind::InvalidValue);
+        assert_eq!(error("--depth four"), ErrorKind::ValueValidation);
+        assert_eq!(error("--depth 33"), ErrorKind::ValueValidation);
+        assert_eq!(error("--type radial --sides 2"), ErrorKind::ValueValidation);
+        assert_eq!(error("--ratios 1/2"), ErrorKind::ValueValidation);
+        assert_eq!(error("--angle 95"), ErrorKind::ValueValidation);
+        assert_eq!(
+            error("--type radial --sides 6 --star 3"),
+            ErrorKind::ValueValidation
+        );
+        assert_eq!(error("--size 0x100"), ErrorKind::ValueValidation);
+        assert!(parse("--type linear --sides 2").is_ok());
+    }
+
+    #[test]
+    fn lsystem_presets_need_the_lsystem_type() {
+        assert_eq!(error("--lsystem dragon"), ErrorKind::ValueValidation);
+        assert_eq!(
+            error("--type radial --lsystem dragon"),
+            ErrorKind::ValueValidation
+        );
+        assert_eq!(
+            parse("--type lsystem").unwrap().curve.lsystem,
+            Some(Preset::Koch)
+        );
+    }
+
+    #[test]
+    fn oversized_curves_are_refused_capped_or_forced() {
+        let huge = "--type radial --sides 50 --depth 15";
+        assert_eq!(error(huge), ErrorKind::ValueValidation);
+
+        let capped = parse(&format!("{huge} --cap")).unwrap();
+        assert_eq!(capped.curve.depth, 8);
+        assert!(capped.warning.is_some());
+
+        assert_eq!(parse(&format!("{huge} --force")).unwrap().curve.depth, 15);
+        assert!(parse(&format!("{huge} --dry-run")).unwrap().dry_run);
+        assert_eq!(
+            error("--depth 6 --max-segments 100"),
+            ErrorKind::ValueValidation
+        );
+    }
+}
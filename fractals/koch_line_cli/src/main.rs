@@ -1,154 +1,145 @@
-use clap::{Arg, Command};
-use nannou::prelude::*;
-use once_cell::sync::OnceCell;
+mod cli;
 
-// Define a global static variable using OnceCell
-static GLOBAL_DATA: OnceCell<Model> = OnceCell::new();
+use cli::Curve;
+use fractal_geometry::drawing::{self, Drawing, Shape, Style};
+use fractal_geometry::fit::fit_into;
+use fractal_geometry::koch::{koch_line_into, koch_radial_into, KochType};
+use fractal_nannou::{draw_polyline, SketchConfig};
+use nannou::prelude::*;
 
 fn main() {
-    let matches = Command::new("Koch Curve Generator")
-        .version("1.0")
-        .about("Draws a Koch Curve")
-        .arg(
-            Arg::new("type")
-                .short('t')
-                .long("type")
-                // .required(true)
-                .help("Type of Koch curve: 'linear' or 'radial'"),
-        )
-        .arg(
-            Arg::new("sides")
-                .short('s')
-                .long("sides")
-                .help("Number of sides for radial (ignored if type is linear)"),
-        )
-        .arg(
-            Arg::new("depth")
-                .short('d')
-                .long("depth")
-                .help("Depth of recursion for the Koch curve"),
-        )
-        .get_matches();
-
-    let koch_type_str = matches.get_one::<String>("type").expect(
-        "type is required\nplease specify with `-t` or `--type`\noptions include: linear or radial",
-    );
-    let koch_type = match koch_type_str.as_str() {
-        "radial" => {
-            let sides = matches
-                .get_one::<String>("sides")
-                .and_then(|s| s.parse().ok())
-                .unwrap_or(5);
-            KochType::Radial(sides)
+    // Bad arguments print a message and exit with a non-zero code
+    let options = cli::parse_from(std::env::args_os()).unwrap_or_else(|err| err.exit());
+
+    if options.dry_run {
+        let curve = &options.curve;
+        println!("Depth {}: {}", curve.depth, curve.estimate(curve.depth));
+        match options.budget.check(&curve.estimate(curve.depth)) {
+            Ok(()) => println!(
+                "Within the limit of {} segments",
+                options.budget.max_segments
+            ),
+            Err(err) => {
+                // Same code clap exits with when it refuses a curve that is not a dry run
+                eprintln!("Needs --cap or --force: {}", err);
+                std::process::exit(2);
+            }
         }
-        _ => KochType::Linear,
-    };
-
-    let depth_str = matches
-        .get_one::<String>("depth")
-        .cloned()
-        .unwrap_or("4".to_string());
-    let depth = depth_str.parse().unwrap_or(4);
+        return;
+    }
+    if let Some(warning) = &options.warning {
+        eprintln!("warning: {warning}");
+    }
 
-    // Initialize the global data once at runtime
-    let _ = GLOBAL_DATA.set(Model {
-        koch_type: koch_type,
-        depth: depth,
-    });
+    let config = SketchConfig::new((800, 600), options.curve).export(options.export);
+    let exported = config.export_with(|width, height| koch(width, height, &config.params));
+    match exported {
+        Ok(true) => return,
+        Ok(false) => {}
+        Err(err) => {
+            eprintln!("error: failed to export: {err}");
+            std::process::exit(1);
+        }
+    }
 
-    nannou::app(model).update(update).view(view).run();
+    config.app(model).update(update).view(view).run();
 }
 
 struct Model {
-    koch_type: KochType,
-    depth: u32,
+    curve: Curve,
+    polylines: Vec<Vec<Point2>>, // Reused for every regeneration
+    size: Option<(f32, f32)>,    // Window size the polylines were generated for
 }
 
-fn model(app: &App) -> Model {
-    // Initialize variables with default values or use Option type
-    let mut koch_type = KochType::Linear; // Default value
-    let mut depth = 4; // Default value
-
-    if let Some(params) = GLOBAL_DATA.get() {
-        // Now params is a reference to the Model instance
-
-        // Access individual fields
-        koch_type = params.koch_type.clone();
-        depth = params.depth;
-
-        // Use the values as needed
-        println!("Koch type: {:?}", koch_type);
-        println!("Depth: {:?}", depth);
-
-        // If you need to match on the KochType
-        match koch_type {
-            KochType::Linear => println!("Koch type is Linear"),
-            KochType::Radial(sides) => println!("Koch type is Radial with {} sides", sides),
-        }
-    } else {
-        // Handle the case where the params have not been initialized
-        println!("Parameters have not been initialized.");
+fn model(app: &App, config: SketchConfig<Curve>) -> Model {
+    let (width, height) = config.window;
+    app.new_window()
+        .size(width, height)
+        .view(view)
+        .build()
+        .unwrap();
+    Model {
+        curve: config.params,
+        polylines: Vec::new(),
+        size: None,
     }
+}
 
-    app.new_window().size(800, 600).view(view).build().unwrap();
-    Model { koch_type, depth }
+fn update(app: &App, model: &mut Model, _update: Update) {
+    // The curve only changes with the window, so it is regenerated when that is resized
+    let window = app.window_rect();
+    let size = Some((window.w(), window.h()));
+    if model.size != size {
+        koch_into(&mut model.polylines, window.w(), window.h(), &model.curve);
+        model.size = size;
+    }
 }
 
-fn update(_app: &App, _model: &mut Model, _update: Update) {
-    // Update logic goes here
+fn koch(width: f32, height: f32, curve: &Curve) -> Drawing {
+    let mut drawing = Drawing::new(width, height, drawing::WHITE);
+    let mut polylines = Vec::new();
+    koch_into(&mut polylines, width, height, curve);
+    for points in polylines {
+        drawing.push(
+            Shape::Polyline(points),
+            Style::stroke(drawing::BLACK, stroke_weight(curve)),
+        );
+    }
+    drawing
 }
 
-#[derive(Clone, Debug)]
-enum KochType {
-    Linear,
-    Radial(u32), // Number of sides
+// L-systems are drawn thinner, they are usually far more detailed
+fn stroke_weight(curve: &Curve) -> f32 {
+    if curve.lsystem.is_some() {
+        2.0
+    } else {
+        4.0
+    }
 }
 
-fn koch_line(draw: &nannou::Draw, start: Point2, end: Point2, depth: u32, koch_type: KochType) {
-    match koch_type {
-        KochType::Linear => {
-            if depth == 0 {
-                draw.line()
-                    .start(start)
-                    .end(end)
-                    .stroke_weight(4.0)
-                    .color(BLACK);
-            } else {
-                // Calculate points for the Koch curve
-                let one_third = start + (end - start) / 3.0;
-                let two_thirds = start + (end - start) * 2.0 / 3.0;
-                let middle_vec = (end - start) / 3.0;
-                let angle = PI / 3.0; // 60 degrees in radians
-                let rotation_matrix = |v: Vec2| -> Vec2 { v.rotate(angle) };
-                let apex = one_third + rotation_matrix(middle_vec);
+// Overwrites `polylines` with the curve fitted to a `width` x `height` window, reusing
+// their buffers for Koch curves
+fn koch_into(polylines: &mut Vec<Vec<Point2>>, width: f32, height: f32, curve: &Curve) {
+    if let Some(preset) = curve.lsystem {
+        // L-systems come out at turtle scale, so stretch them over most of the window
+        let turtle = preset
+            .lsystem()
+            .seed(curve.seed)
+            .polylines(curve.depth, pt2(0.0, 0.0));
+        *polylines = fit_into(&turtle, pt2(0.0, 0.0), width * 0.9, height * 0.9);
+        return;
+    }
 
-                // Recursively draw the four new line segments
-                koch_line(draw, start, one_third, depth - 1, KochType::Linear);
-                koch_line(draw, one_third, apex, depth - 1, KochType::Linear);
-                koch_line(draw, apex, two_thirds, depth - 1, KochType::Linear);
-                koch_line(draw, two_thirds, end, depth - 1, KochType::Linear);
-            }
+    match curve.koch_type {
+        KochType::Linear => {
+            // Set the starting and ending points for the Koch curve
+            let start = pt2(-width / 2.0, 0.0);
+            let end = pt2(width / 2.0, 0.0);
+            polylines.resize_with(1, Vec::new);
+            koch_line_into(
+                &mut polylines[0],
+                start,
+                end,
+                curve.depth,
+                curve.koch_type,
+                &curve.generator,
+            );
         }
-        KochType::Radial(sides) => {
-            let center = pt2(0.0, 0.0); // Center of the circle
-            let radius = 300.0; // Radius of the radial Koch curve
-
-            for i in 0..sides {
-                let angle_start = i as f32 * TAU / sides as f32;
-                let angle_end = (i as f32 + 1.0) * TAU / sides as f32;
-
-                let start = pt2(
-                    center.x + angle_start.cos() * radius,
-                    center.y + angle_start.sin() * radius,
-                );
-
-                let end = pt2(
-                    center.x + angle_end.cos() * radius,
-                    center.y + angle_end.sin() * radius,
-                );
-
-                koch_line(draw, start, end, depth, KochType::Linear); // Use Linear here to avoid infinite recursion
-            }
+        KochType::Radial(_) if curve.fit => {
+            // Largest box around the layout's center that stays inside the window, less
+            // the stroke
+            let center = curve.layout.center;
+            let width = (width - 2.0 * center.x.abs() - 4.0).max(0.0);
+            let height = (height - 2.0 * center.y.abs() - 4.0).max(0.0);
+            let layout =
+                curve
+                    .layout
+                    .clone()
+                    .fit_into(center, width, height, curve.depth, &curve.generator);
+            koch_radial_into(polylines, &layout, curve.depth, &curve.generator);
+        }
+        KochType::Radial(_) => {
+            koch_radial_into(polylines, &curve.layout, curve.depth, &curve.generator)
         }
     }
 }
@@ -158,15 +149,10 @@ fn view(app: &App, model: &Model, frame: Frame) {
     let draw = app.draw();
     draw.background().color(WHITE);
 
-    let boundary = app.window_rect();
-    let middle = boundary.xy();
-
-    // Set the starting and ending points for the Koch curve
-    let start = pt2(middle.x - boundary.w() / 2.0, middle.y);
-    let end = pt2(middle.x + boundary.w() / 2.0, middle.y);
-
-    // Draw the Koch curve based on the specified type and depth
-    koch_line(&draw, start, end, model.depth, model.koch_type.clone());
+    // Draw the Koch curve generated in `update`
+    for points in &model.polylines {
+        draw_polyline(&draw, points, stroke_weight(&model.curve), BLACK);
+    }
 
     // Finish and present the frame
     draw.to_frame(app, &frame).unwrap();